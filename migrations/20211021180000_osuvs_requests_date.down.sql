@@ -0,0 +1,2 @@
+ALTER TABLE osuvs_requests
+    DROP COLUMN requested_at;
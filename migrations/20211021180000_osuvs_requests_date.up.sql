@@ -0,0 +1,2 @@
+ALTER TABLE osuvs_requests
+    ADD COLUMN requested_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP;
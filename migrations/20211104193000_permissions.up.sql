@@ -0,0 +1,12 @@
+CREATE TABLE permissions (
+    permission VARCHAR(32) NOT NULL,
+    target_id INT8 NOT NULL,
+    is_role BOOL NOT NULL,
+    PRIMARY KEY (permission, target_id)
+);
+
+INSERT INTO permissions (permission, target_id, is_role) VALUES
+    ('osuvs_admin', 185773647246524416, FALSE),
+    ('osuvs_admin', 219905108316520448, FALSE),
+    ('osuvs_admin', 127449164224266240, FALSE),
+    ('osuvs_admin', 250998032856776704, FALSE);
@@ -0,0 +1 @@
+ALTER TABLE osuvs_maps DROP COLUMN mode;
@@ -0,0 +1 @@
+ALTER TABLE osuvs_maps ADD COLUMN mode INT2 NOT NULL DEFAULT 0;
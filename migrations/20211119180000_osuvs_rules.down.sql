@@ -0,0 +1,5 @@
+ALTER TABLE osuvs_maps
+    DROP COLUMN required_mods,
+    DROP COLUMN forbidden_mods,
+    DROP COLUMN metric,
+    DROP COLUMN tiebreak;
@@ -0,0 +1,5 @@
+ALTER TABLE osuvs_maps
+    ADD COLUMN required_mods INT4 NOT NULL DEFAULT 0,
+    ADD COLUMN forbidden_mods INT4 NOT NULL DEFAULT 536870912,
+    ADD COLUMN metric INT2 NOT NULL DEFAULT 0,
+    ADD COLUMN tiebreak INT2;
@@ -0,0 +1,8 @@
+CREATE TABLE osuvs_seasons (
+    season_id SERIAL PRIMARY KEY,
+    name VARCHAR(64) NOT NULL UNIQUE,
+    start_date TIMESTAMPTZ NOT NULL,
+    end_date TIMESTAMPTZ NOT NULL,
+    points INT4[] NOT NULL DEFAULT '{25,18,15,12,10,8,6,4,2,1}',
+    participation_points INT4 NOT NULL DEFAULT 1
+);
@@ -0,0 +1,4 @@
+CREATE TABLE osuvs_tracking (
+    user_id INT4 NOT NULL PRIMARY KEY,
+    last_checked TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,3 @@
+CREATE TABLE osuvs_notifications (
+    discord_id INT8 NOT NULL PRIMARY KEY
+);
@@ -0,0 +1,13 @@
+CREATE TABLE osuvs_announcements (
+    beatmap_id INT4 NOT NULL,
+    kind INT2 NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (beatmap_id) REFERENCES osuvs_maps(beatmap_id),
+    PRIMARY KEY (beatmap_id, kind)
+);
+
+-- Rounds before the ledger existed were already announced
+INSERT INTO osuvs_announcements (beatmap_id, kind)
+    SELECT beatmap_id, 0 FROM osuvs_maps WHERE start_date <= CURRENT_TIMESTAMP;
+INSERT INTO osuvs_announcements (beatmap_id, kind)
+    SELECT beatmap_id, 1 FROM osuvs_maps WHERE end_date <= CURRENT_TIMESTAMP;
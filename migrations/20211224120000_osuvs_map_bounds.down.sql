@@ -0,0 +1 @@
+DROP TABLE osuvs_map_bounds;
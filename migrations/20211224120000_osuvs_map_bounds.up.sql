@@ -0,0 +1,7 @@
+CREATE TABLE osuvs_map_bounds (
+    mode INT2 NOT NULL PRIMARY KEY,
+    min_stars FLOAT4,
+    max_stars FLOAT4,
+    min_length INT4,
+    max_length INT4
+);
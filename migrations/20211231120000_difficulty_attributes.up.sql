@@ -0,0 +1,8 @@
+CREATE TABLE difficulty_attributes (
+    map_id INT4 NOT NULL,
+    mods INT4 NOT NULL,
+    mode INT2 NOT NULL,
+    checksum VARCHAR(32) NOT NULL,
+    attributes JSON NOT NULL,
+    PRIMARY KEY (map_id, mods, mode)
+);
@@ -0,0 +1,3 @@
+DROP TABLE osuvs_division_members;
+DROP TABLE osuvs_map_divisions;
+DROP TABLE osuvs_division_config;
@@ -0,0 +1,20 @@
+CREATE TABLE osuvs_division_config (
+    mode INT2 NOT NULL PRIMARY KEY,
+    metric INT2 NOT NULL,
+    thresholds INT4[] NOT NULL
+);
+
+CREATE TABLE osuvs_map_divisions (
+    beatmap_id INT4 NOT NULL PRIMARY KEY,
+    metric INT2 NOT NULL,
+    thresholds INT4[] NOT NULL,
+    FOREIGN KEY (beatmap_id) REFERENCES osuvs_maps(beatmap_id)
+);
+
+CREATE TABLE osuvs_division_members (
+    beatmap_id INT4 NOT NULL,
+    user_id INT4 NOT NULL,
+    division INT2 NOT NULL,
+    FOREIGN KEY (beatmap_id) REFERENCES osuvs_maps(beatmap_id),
+    PRIMARY KEY (beatmap_id, user_id)
+);
@@ -0,0 +1,6 @@
+CREATE TABLE link_codes (
+    discord_id INT8 NOT NULL PRIMARY KEY,
+    osu_id INT8 NOT NULL,
+    code VARCHAR(8) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP TABLE presence_follows;
+DROP TABLE presence_hidden;
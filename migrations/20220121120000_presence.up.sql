@@ -0,0 +1,9 @@
+CREATE TABLE presence_hidden (
+    discord_id INT8 NOT NULL PRIMARY KEY
+);
+
+CREATE TABLE presence_follows (
+    follower INT8 NOT NULL,
+    discord_id INT8 NOT NULL,
+    PRIMARY KEY (follower, discord_id)
+);
@@ -0,0 +1,3 @@
+DELETE FROM osuvs_announcements WHERE sent_at IS NULL;
+ALTER TABLE osuvs_announcements ALTER COLUMN sent_at SET DEFAULT CURRENT_TIMESTAMP;
+ALTER TABLE osuvs_announcements ALTER COLUMN sent_at SET NOT NULL;
@@ -0,0 +1,3 @@
+-- Claimed announcements stay pending until they were sent successfully
+ALTER TABLE osuvs_announcements ALTER COLUMN sent_at DROP NOT NULL;
+ALTER TABLE osuvs_announcements ALTER COLUMN sent_at DROP DEFAULT;
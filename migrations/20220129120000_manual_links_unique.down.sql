@@ -0,0 +1 @@
+DROP INDEX manual_links_osu_id;
@@ -0,0 +1,5 @@
+-- Only one discord user can stay linked to each osu! account
+DELETE FROM manual_links a USING manual_links b
+    WHERE a.osu_id = b.osu_id AND a.discord_id > b.discord_id;
+
+CREATE UNIQUE INDEX manual_links_osu_id ON manual_links (osu_id);
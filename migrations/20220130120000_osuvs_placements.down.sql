@@ -0,0 +1,2 @@
+DROP VIEW osuvs_placements;
+DROP FUNCTION osuvs_metric_value;
@@ -0,0 +1,21 @@
+-- Value of a score for an osuvs metric such that higher values are better
+CREATE FUNCTION osuvs_metric_value(score JSON, metric INT2) RETURNS FLOAT8 AS $$
+    SELECT CASE metric
+        WHEN 1 THEN (score->>'accuracy')::FLOAT8
+        WHEN 2 THEN COALESCE((score->>'pp')::FLOAT8, 0)
+        WHEN 3 THEN (score->>'max_combo')::FLOAT8
+        WHEN 4 THEN -(score->'statistics'->>'count_miss')::FLOAT8
+        ELSE (score->>'score')::FLOAT8
+    END
+$$ LANGUAGE SQL IMMUTABLE;
+
+-- Placement of every score within its round, ranked by the rules of the round
+CREATE VIEW osuvs_placements AS
+    SELECT s.beatmap_id, s.user_id, s.score, RANK() OVER (
+        PARTITION BY s.beatmap_id ORDER BY
+            osuvs_metric_value(s.score, m.metric) DESC,
+            CASE WHEN m.tiebreak IS NULL THEN 0
+                ELSE osuvs_metric_value(s.score, m.tiebreak) END DESC,
+            (s.score->>'created_at')::TIMESTAMPTZ
+    ) AS placement
+    FROM osuvs_scores AS s JOIN osuvs_maps AS m ON m.beatmap_id = s.beatmap_id;
@@ -12,6 +12,7 @@ pub struct Command {
     run_name: Ident,
     args_name: Option<Ident>,
     options_name: Option<Ident>,
+    permission: Option<LitStr>,
 }
 
 impl TryFrom<Base> for Command {
@@ -23,6 +24,7 @@ impl TryFrom<Base> for Command {
         let mut run_name = None;
         let mut args_name = None;
         let mut options_name = None;
+        let mut permission = None;
 
         for attr in base.attributes {
             match attr.parse_meta()? {
@@ -63,11 +65,20 @@ impl TryFrom<Base> for Command {
                             other => {
                                 let message = r#"`options` attribute must be of the form `#[options = "..."]`"#;
 
+                                return Err(SynError::new(other.span(), message));
+                            }
+                        }
+                    } else if m.path == parse_quote!(permission) {
+                        match m.lit {
+                            Lit::Str(lit) => permission = Some(lit),
+                            other => {
+                                let message = r#"`permission` attribute must be of the form `#[permission = "..."]`"#;
+
                                 return Err(SynError::new(other.span(), message));
                             }
                         }
                     } else {
-                        let message = "invalid attribute key, expected either `args`, `description`, `name`, `options`, or `run`";
+                        let message = "invalid attribute key, expected either `args`, `description`, `name`, `options`, `permission`, or `run`";
                         let span = m.path.get_ident().map_or_else(Span::call_site, Ident::span);
 
                         return Err(SynError::new(span, message));
@@ -108,6 +119,7 @@ impl TryFrom<Base> for Command {
             run_name,
             args_name,
             options_name,
+            permission,
         };
 
         Ok(command)
@@ -123,6 +135,7 @@ impl ToTokens for Command {
             run_name,
             args_name,
             options_name,
+            permission,
         } = self;
 
         let fut_name = format_ident!("{}Future", name);
@@ -147,6 +160,14 @@ impl ToTokens for Command {
             }
         };
 
+        let permission_check = permission.as_ref().map(|permission| {
+            quote! {
+                if !crate::commands::check_permission(&ctx, &command, #permission).await? {
+                    return crate::commands::permission_denied(&ctx, &command).await;
+                }
+            }
+        });
+
         tokens.extend(base_stream);
 
         let define_stream = if let Some(options_name) = options_name {
@@ -195,9 +216,14 @@ impl ToTokens for Command {
 
                         let data = ::std::mem::take(&mut command.data);
 
-                        let fut = #args_name::parse_options(Arc::clone(&ctx), data)
-                            .and_then(|args| #run_name(ctx, command, args))
-                            .map_err(Box::new)
+                        let fut = async move {
+                            #permission_check
+
+                            let args = #args_name::parse_options(Arc::clone(&ctx), data).await?;
+
+                            #run_name(ctx, command, args).await
+                        }
+                        .map_err(Box::new)
                             .map_err(|src| crate::Error::Command {
                                 name: #cmd_name,
                                 src,
@@ -213,8 +239,12 @@ impl ToTokens for Command {
                     pub fn run(ctx: ::std::sync::Arc<crate::Context>, command: ::twilight_model::application::interaction::ApplicationCommand) -> #fut_name<'static> {
                         use futures::TryFutureExt;
 
-                        let fut = #run_name(ctx, command)
-                            .map_err(Box::new)
+                        let fut = async move {
+                            #permission_check
+
+                            #run_name(ctx, command).await
+                        }
+                        .map_err(Box::new)
                             .map_err(|src| crate::Error::Command {
                                 name: #cmd_name,
                                 src,
@@ -20,6 +20,8 @@ use syn::{
 /// - `#[name = "..."]` for the command name. Defaults to the lowercase struct name.
 /// - `#[options = "..."]` for the function name that returns the command options as `Vec<CommandOption>`.
 /// If none is specified the defined command won't have options.
+/// - `#[permission = "..."]` for the permission that is required to use the command.
+/// Authors without the permission receive an error response and the command won't run.
 /// - `#[run = "..."` for the function name that runs the command. Defaults to the lowercase struct name.
 ///
 /// For a given command struct `C` this macro enables:
@@ -0,0 +1,281 @@
+use std::{
+    collections::HashMap,
+    env,
+    io::ErrorKind,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use md5::{Digest, Md5};
+use parking_lot::Mutex;
+use rosu_v2::Osu as OsuClient;
+use tokio::{fs, sync::Mutex as AsyncMutex};
+
+use crate::{error::MapDownloadError, utils::osu::request_beatmap_file};
+
+/// Size budget of the folder so the SD card doesn't fill up
+const BUDGET: u64 = 512 * 1024 * 1024;
+
+/// How long a checksum that was compared with the osu! API is trusted
+const VERIFY_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+struct StoredFile {
+    size: u64,
+    last_used: SystemTime,
+    checksum: String,
+    verified_at: Option<Instant>,
+}
+
+pub struct BeatmapFile {
+    pub content: Vec<u8>,
+    pub checksum: String,
+}
+
+/// `.osu` files stored in the `BEATMAPS_FOLDER`.
+///
+/// Files are verified against the checksum of the osu! API and downloaded
+/// again if they're outdated or broken. Once the folder exceeds its budget,
+/// the least recently used files are removed.
+pub struct BeatmapStore {
+    folder: PathBuf,
+    files: Mutex<HashMap<u32, StoredFile>>,
+    downloads: Mutex<HashMap<u32, Arc<AsyncMutex<()>>>>,
+}
+
+impl BeatmapStore {
+    pub async fn new() -> Result<Self, MapDownloadError> {
+        let folder = env::var("BEATMAPS_FOLDER")
+            .map(PathBuf::from)
+            .map_err(|_| MapDownloadError::MissingFolder)?;
+
+        fs::create_dir_all(&folder).await?;
+
+        let mut files = HashMap::new();
+        let mut entries = fs::read_dir(&folder).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            // Leftover of an interrupted download
+            if name.ends_with(".tmp") {
+                fs::remove_file(&path).await?;
+
+                continue;
+            }
+
+            let map_id = match name.strip_suffix(".osu").map(str::parse) {
+                Some(Ok(map_id)) => map_id,
+                _ => continue,
+            };
+
+            let content = fs::read(&path).await?;
+            let metadata = entry.metadata().await?;
+
+            let file = StoredFile {
+                size: metadata.len(),
+                last_used: metadata.modified().unwrap_or_else(|_| SystemTime::now()),
+                checksum: md5_checksum(&content),
+                verified_at: None,
+            };
+
+            files.insert(map_id, file);
+        }
+
+        info!("Found {} stored beatmap files", files.len());
+
+        let store = Self {
+            folder,
+            files: Mutex::new(files),
+            downloads: Mutex::new(HashMap::new()),
+        };
+
+        store.evict(None).await;
+
+        Ok(store)
+    }
+
+    /// Get the verified content of the map's file, downloading it if necessary.
+    ///
+    /// If no checksum is given, it will be requested from the osu! API
+    /// unless the file was verified recently.
+    pub async fn get(
+        &self,
+        osu: &OsuClient,
+        map_id: u32,
+        checksum: Option<&str>,
+    ) -> Result<BeatmapFile, MapDownloadError> {
+        // Concurrent requests for the same map wait for the first one
+        let lock = Arc::clone(self.downloads.lock().entry(map_id).or_default());
+        let guard = lock.lock().await;
+        let result = self.get_locked(osu, map_id, checksum).await;
+        drop(guard);
+
+        let mut downloads = self.downloads.lock();
+
+        // Only the map and this function hold the lock
+        if Arc::strong_count(&lock) == 2 {
+            downloads.remove(&map_id);
+        }
+
+        result
+    }
+
+    async fn get_locked(
+        &self,
+        osu: &OsuClient,
+        map_id: u32,
+        checksum: Option<&str>,
+    ) -> Result<BeatmapFile, MapDownloadError> {
+        let (expected, fresh) = match (checksum, self.verified_checksum(map_id)) {
+            (Some(checksum), _) => (Some(checksum.to_owned()), true),
+            (None, Some(checksum)) => (Some(checksum), false),
+            (None, None) => (osu.beatmap().map_id(map_id).await?.checksum, true),
+        };
+
+        let path = self.path(map_id);
+
+        match fs::read(&path).await {
+            Ok(content) => {
+                let actual = md5_checksum(&content);
+
+                let valid = match expected {
+                    Some(ref expected) => *expected == actual,
+                    None => is_osu_file(&content),
+                };
+
+                if valid {
+                    self.insert(
+                        map_id,
+                        content.len() as u64,
+                        &actual,
+                        fresh && expected.is_some(),
+                    );
+
+                    return Ok(BeatmapFile {
+                        content,
+                        checksum: actual,
+                    });
+                }
+
+                info!("Stored {map_id}.osu is outdated or broken, downloading again");
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        let content = request_beatmap_file(map_id).await?.to_vec();
+        let actual = md5_checksum(&content);
+
+        if let Some(expected) = expected.as_ref().filter(|&expected| *expected != actual) {
+            return Err(MapDownloadError::ChecksumMismatch {
+                map_id,
+                expected: expected.to_owned(),
+                actual,
+            });
+        }
+
+        // Write to a temporary file first so that an interrupted
+        // write never leaves a truncated file behind
+        let tmp_path = self.folder.join(format!("{map_id}.osu.tmp"));
+        fs::write(&tmp_path, &content).await?;
+        fs::rename(&tmp_path, &path).await?;
+        info!("Downloaded {map_id}.osu successfully");
+
+        self.insert(map_id, content.len() as u64, &actual, expected.is_some());
+        self.evict(Some(map_id)).await;
+
+        Ok(BeatmapFile {
+            content,
+            checksum: actual,
+        })
+    }
+
+    fn path(&self, map_id: u32) -> PathBuf {
+        self.folder.join(format!("{map_id}.osu"))
+    }
+
+    /// Checksum of the stored file if it was verified recently
+    pub fn verified_checksum(&self, map_id: u32) -> Option<String> {
+        let files = self.files.lock();
+        let file = files.get(&map_id)?;
+
+        match file.verified_at {
+            Some(verified_at) if verified_at.elapsed() < VERIFY_INTERVAL => {
+                Some(file.checksum.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn insert(&self, map_id: u32, size: u64, checksum: &str, verified: bool) {
+        let mut files = self.files.lock();
+
+        let verified_at = if verified {
+            Some(Instant::now())
+        } else {
+            files
+                .get(&map_id)
+                .filter(|file| file.checksum == checksum)
+                .and_then(|file| file.verified_at)
+        };
+
+        let file = StoredFile {
+            size,
+            last_used: SystemTime::now(),
+            checksum: checksum.to_owned(),
+            verified_at,
+        };
+
+        files.insert(map_id, file);
+    }
+
+    /// Remove the least recently used files until the budget is met
+    async fn evict(&self, keep: Option<u32>) {
+        let evicted = {
+            let files = self.files.lock();
+            let downloads = self.downloads.lock();
+            let mut total: u64 = files.values().map(|file| file.size).sum();
+
+            let mut candidates: Vec<_> = files
+                .iter()
+                .filter(|(map_id, _)| Some(**map_id) != keep && !downloads.contains_key(map_id))
+                .map(|(map_id, file)| (*map_id, file.size, file.last_used))
+                .collect();
+
+            candidates.sort_unstable_by_key(|(.., last_used)| *last_used);
+            let mut evicted = Vec::new();
+
+            for (map_id, size, _) in candidates {
+                if total <= BUDGET {
+                    break;
+                }
+
+                total -= size;
+                evicted.push(map_id);
+            }
+
+            evicted
+        };
+
+        for map_id in evicted {
+            if let Err(why) = fs::remove_file(self.path(map_id)).await {
+                unwind_error!(warn, why, "Failed to remove {}.osu: {}", map_id);
+            }
+
+            self.files.lock().remove(&map_id);
+        }
+    }
+}
+
+fn md5_checksum(content: &[u8]) -> String {
+    format!("{:x}", Md5::digest(content))
+}
+
+fn is_osu_file(content: &[u8]) -> bool {
+    let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+
+    content.starts_with(b"osu file format")
+}
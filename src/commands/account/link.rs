@@ -0,0 +1,214 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use rand::Rng;
+use rosu_v2::prelude::OsuError;
+use twilight_model::application::{
+    command::{ChoiceCommandOptionData, CommandOption, OptionsCommandOptionData},
+    interaction::{
+        application_command::{CommandData, CommandDataOption},
+        ApplicationCommand,
+    },
+};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder, OSU_BASE},
+};
+
+/// How long a code can be used to verify the account
+const CODE_DURATION_MINUTES: i64 = 10;
+const CODE_LENGTH: usize = 8;
+/// Characters that can't be confused with each other
+const CODE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+#[command]
+#[args = "LinkArgs"]
+#[description = "Link your osu! account to your discord account"]
+#[options = "link_options"]
+pub struct Link;
+
+pub enum LinkArgs {
+    Account(String),
+    Verify(String),
+}
+
+impl LinkArgs {
+    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
+        for option in data.options {
+            if let CommandDataOption::SubCommand { name, options } = option {
+                let value = options.into_iter().find_map(|option| match option {
+                    CommandDataOption::String { value, .. } => Some(value),
+                    _ => None,
+                });
+
+                match (name.as_str(), value) {
+                    ("account", Some(name)) => return Ok(Self::Account(name)),
+                    ("verify", Some(code)) => return Ok(Self::Verify(code)),
+                    _ => (),
+                }
+            }
+        }
+
+        unreachable!();
+    }
+}
+
+fn link_options() -> Vec<CommandOption> {
+    let name_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify your osu! username".to_string(),
+        name: "name".to_string(),
+        required: true,
+    };
+
+    let account = OptionsCommandOptionData {
+        description: "Receive a verification code in your osu! chat".to_string(),
+        name: "account".to_string(),
+        options: vec![CommandOption::String(name_option)],
+        required: false,
+    };
+
+    let code_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify the code that was sent to you in osu!".to_string(),
+        name: "code".to_string(),
+        required: true,
+    };
+
+    let verify = OptionsCommandOptionData {
+        description: "Verify your osu! account with the code that was sent to you".to_string(),
+        name: "verify".to_string(),
+        options: vec![CommandOption::String(code_option)],
+        required: false,
+    };
+
+    vec![
+        CommandOption::SubCommand(account),
+        CommandOption::SubCommand(verify),
+    ]
+}
+
+async fn link(ctx: Arc<Context>, command: ApplicationCommand, args: LinkArgs) -> BotResult<()> {
+    match args {
+        LinkArgs::Account(name) => account(ctx, command, name).await,
+        LinkArgs::Verify(code) => verify(ctx, command, code).await,
+    }
+}
+
+async fn account(ctx: Arc<Context>, command: ApplicationCommand, name: String) -> BotResult<()> {
+    let discord_id = command.user_id()?;
+
+    if ctx.database.get_manual_link(discord_id).await?.is_some() {
+        let content =
+            "You already linked an osu! account, use `/unlink` first to link another one!";
+
+        return error(&ctx, &command, content).await;
+    }
+
+    let user = match ctx.osu.user(name.as_str()).await {
+        Ok(user) => user,
+        Err(OsuError::NotFound) => {
+            let content = format!("Could not find the osu! user `{}`!", name);
+
+            return error(&ctx, &command, &content).await;
+        }
+        Err(why) => return Err(why.into()),
+    };
+
+    if ctx
+        .database
+        .get_manual_link_by_osu_id(user.user_id)
+        .await?
+        .is_some()
+    {
+        let content = format!(
+            "The osu! account `{}` is already linked to another discord account!",
+            user.username
+        );
+
+        return error(&ctx, &command, &content).await;
+    }
+
+    let code = generate_code();
+    let expires_at = Utc::now() + Duration::minutes(CODE_DURATION_MINUTES);
+
+    ctx.database
+        .insert_link_code(discord_id, user.user_id, &code, expires_at)
+        .await?;
+
+    let message = format!(
+        "Your code to link your osu! account on discord is {}. Use /link verify with it \
+        within the next {} minutes. If you did not request this, just ignore this message.",
+        code, CODE_DURATION_MINUTES
+    );
+
+    if !ctx.irc.send_private_message(&user.username, &message)? {
+        ctx.database.remove_link_code(discord_id).await?;
+        let content = "I'm not connected to osu! chat right now, please try again later!";
+
+        return error(&ctx, &command, content).await;
+    }
+
+    info!(
+        "Sent link code for osu! user {} to discord user {}",
+        user.username, discord_id
+    );
+
+    let content = format!(
+        "I sent a code to [{}]({}users/{}) in osu! chat. \
+        Use `/link verify` with it within the next {} minutes to link your account.",
+        user.username, OSU_BASE, user.user_id, CODE_DURATION_MINUTES
+    );
+    let builder = MessageBuilder::new().embed(content).ephemeral();
+
+    command.create_message(&ctx, builder).await
+}
+
+async fn verify(ctx: Arc<Context>, command: ApplicationCommand, code: String) -> BotResult<()> {
+    let discord_id = command.user_id()?;
+
+    let osu_id = match ctx.database.get_link_code(discord_id).await? {
+        Some((osu_id, expected)) if expected.eq_ignore_ascii_case(code.trim()) => osu_id,
+        Some(_) => return error(&ctx, &command, "That code is not correct!").await,
+        None => {
+            let content = "You have no pending code, use `/link account` to receive a new one!";
+
+            return error(&ctx, &command, content).await;
+        }
+    };
+
+    ctx.database.remove_link_code(discord_id).await?;
+
+    if !ctx.link_user(discord_id, osu_id).await? {
+        let content =
+            "This osu! account has been linked to another discord account in the meantime!";
+
+        return error(&ctx, &command, content).await;
+    }
+
+    info!("Linked discord user {} to osu! user {}", discord_id, osu_id);
+
+    let content = format!(
+        "Your discord account is now linked to [this osu! account]({}users/{})!",
+        OSU_BASE, osu_id
+    );
+    let builder = MessageBuilder::new().embed(content).ephemeral();
+
+    command.create_message(&ctx, builder).await
+}
+
+async fn error(ctx: &Context, command: &ApplicationCommand, content: &str) -> BotResult<()> {
+    let builder = MessageBuilder::new().error(content).ephemeral();
+
+    command.create_message(ctx, builder).await
+}
+
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+
+    (0..CODE_LENGTH)
+        .map(|_| CODE_CHARS[rng.gen_range(0..CODE_CHARS.len())] as char)
+        .collect()
+}
@@ -0,0 +1,5 @@
+mod link;
+mod unlink;
+
+pub use link::Link;
+pub use unlink::Unlink;
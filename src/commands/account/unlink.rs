@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder, OSU_BASE},
+};
+
+#[command]
+#[description = "Unlink your osu! account from your discord account"]
+pub struct Unlink;
+
+async fn unlink(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    let discord_id = command.user_id()?;
+
+    let builder = match ctx.unlink_user(discord_id).await? {
+        Some(osu_id) => {
+            info!(
+                "Unlinked discord user {} from osu! user {}",
+                discord_id, osu_id
+            );
+
+            let content = format!(
+                "Your discord account is no longer linked to [this osu! account]({}users/{})",
+                OSU_BASE, osu_id
+            );
+
+            MessageBuilder::new().embed(content).ephemeral()
+        }
+        None => MessageBuilder::new()
+            .error("You have not linked an osu! account!")
+            .ephemeral(),
+    };
+
+    command.create_message(&ctx, builder).await
+}
@@ -0,0 +1,3 @@
+mod permissions;
+
+pub use permissions::Permissions;
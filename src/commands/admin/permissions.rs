@@ -0,0 +1,254 @@
+use std::{fmt::Write, sync::Arc};
+
+use twilight_model::{
+    application::{
+        command::{
+            BaseCommandOptionData, ChoiceCommandOptionData, CommandOption, CommandOptionChoice,
+            OptionsCommandOptionData,
+        },
+        interaction::{
+            application_command::{CommandData, CommandDataOption},
+            ApplicationCommand,
+        },
+    },
+    id::{RoleId, UserId},
+};
+
+use crate::{
+    commands::{PERMISSIONS, PERMISSION_ADMIN},
+    context::Context,
+    database::PermissionTarget,
+    error::BotResult,
+    utils::{ApplicationCommandExt, Author, EmbedBuilder, MessageBuilder},
+};
+
+#[command]
+#[args = "PermissionsArgs"]
+#[description = "Manage who is allowed to use admin commands"]
+#[options = "permissions_options"]
+#[permission = "admin"]
+pub struct Permissions;
+
+pub enum PermissionsArgs {
+    Grant {
+        permission: String,
+        target: Option<PermissionTarget>,
+    },
+    List,
+    Revoke {
+        permission: String,
+        target: Option<PermissionTarget>,
+    },
+}
+
+impl PermissionsArgs {
+    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
+        for option in data.options {
+            if let CommandDataOption::SubCommand { name, options } = option {
+                match name.as_str() {
+                    "grant" => {
+                        let (permission, target) = Self::parse_target_options(options);
+
+                        return Ok(Self::Grant { permission, target });
+                    }
+                    "list" => return Ok(Self::List),
+                    "revoke" => {
+                        let (permission, target) = Self::parse_target_options(options);
+
+                        return Ok(Self::Revoke { permission, target });
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        unreachable!()
+    }
+
+    fn parse_target_options(options: Vec<CommandDataOption>) -> (String, Option<PermissionTarget>) {
+        let mut permission = None;
+        let mut target = None;
+
+        for option in options {
+            // User and role options are received as strings containing the id
+            if let CommandDataOption::String { name, value } = option {
+                match name.as_str() {
+                    "permission" => permission = Some(value),
+                    "role" => {
+                        target = value
+                            .parse()
+                            .ok()
+                            .map(|id| PermissionTarget::Role(RoleId(id)))
+                    }
+                    "user" => {
+                        target = value
+                            .parse()
+                            .ok()
+                            .map(|id| PermissionTarget::User(UserId(id)))
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        (permission.unwrap_or_default(), target)
+    }
+}
+
+fn permissions_options() -> Vec<CommandOption> {
+    let target_options = || {
+        let choices = PERMISSIONS
+            .iter()
+            .map(|&permission| CommandOptionChoice::String {
+                name: permission.to_owned(),
+                value: permission.to_owned(),
+            })
+            .collect();
+
+        let permission = ChoiceCommandOptionData {
+            choices,
+            description: "Specify the permission".to_string(),
+            name: "permission".to_string(),
+            required: true,
+        };
+
+        let role = BaseCommandOptionData {
+            description: "Specify a role".to_string(),
+            name: "role".to_string(),
+            required: false,
+        };
+
+        let user = BaseCommandOptionData {
+            description: "Specify a user".to_string(),
+            name: "user".to_string(),
+            required: false,
+        };
+
+        vec![
+            CommandOption::String(permission),
+            CommandOption::Role(role),
+            CommandOption::User(user),
+        ]
+    };
+
+    let grant = OptionsCommandOptionData {
+        description: "Grant a permission to a role or user".to_string(),
+        name: "grant".to_string(),
+        options: target_options(),
+        required: false,
+    };
+
+    let list = OptionsCommandOptionData {
+        description: "List all granted permissions".to_string(),
+        name: "list".to_string(),
+        options: vec![],
+        required: false,
+    };
+
+    let revoke = OptionsCommandOptionData {
+        description: "Revoke a permission from a role or user".to_string(),
+        name: "revoke".to_string(),
+        options: target_options(),
+        required: false,
+    };
+
+    vec![
+        CommandOption::SubCommand(grant),
+        CommandOption::SubCommand(list),
+        CommandOption::SubCommand(revoke),
+    ]
+}
+
+async fn permissions(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: PermissionsArgs,
+) -> BotResult<()> {
+    match args {
+        PermissionsArgs::Grant { permission, target } => {
+            let target = match target {
+                Some(target) => target,
+                None => return missing_target(&ctx, &command).await,
+            };
+
+            let content = if ctx.database.insert_permission(&permission, target).await? {
+                info!("Granted permission `{}` to {:?}", permission, target);
+
+                format!("Granted the `{}` permission to {}", permission, target)
+            } else {
+                format!("{} already has the `{}` permission", target, permission)
+            };
+
+            let builder = MessageBuilder::new().embed(content).ephemeral();
+
+            command.create_message(&ctx, builder).await
+        }
+        PermissionsArgs::List => {
+            let permissions = ctx.database.get_permissions().await?;
+            let mut description = String::new();
+
+            for &name in PERMISSIONS.iter() {
+                let _ = write!(description, "**{}:**", name);
+
+                let mut targets = permissions
+                    .iter()
+                    .filter(|(permission, _)| permission == name)
+                    .map(|(_, target)| target)
+                    .peekable();
+
+                if targets.peek().is_none() {
+                    description.push_str(" -");
+                }
+
+                for target in targets {
+                    let _ = write!(description, " {}", target);
+                }
+
+                description.push('\n');
+            }
+
+            let builder = EmbedBuilder::new()
+                .author(Author::new("Granted permissions"))
+                .description(description);
+            let builder = MessageBuilder::new().embed(builder).ephemeral();
+
+            command.create_message(&ctx, builder).await
+        }
+        PermissionsArgs::Revoke { permission, target } => {
+            let target = match target {
+                Some(target) => target,
+                None => return missing_target(&ctx, &command).await,
+            };
+
+            if permission == PERMISSION_ADMIN
+                && target == PermissionTarget::User(command.user_id()?)
+            {
+                let builder = MessageBuilder::new()
+                    .error("You can not revoke your own admin permission!")
+                    .ephemeral();
+
+                return command.create_message(&ctx, builder).await;
+            }
+
+            let content = if ctx.database.remove_permission(&permission, target).await? {
+                info!("Revoked permission `{}` from {:?}", permission, target);
+
+                format!("Revoked the `{}` permission from {}", permission, target)
+            } else {
+                format!("{} does not have the `{}` permission", target, permission)
+            };
+
+            let builder = MessageBuilder::new().embed(content).ephemeral();
+
+            command.create_message(&ctx, builder).await
+        }
+    }
+}
+
+async fn missing_target(ctx: &Context, command: &ApplicationCommand) -> BotResult<()> {
+    let builder = MessageBuilder::new()
+        .error("You must specify either a role or a user!")
+        .ephemeral();
+
+    command.create_message(ctx, builder).await
+}
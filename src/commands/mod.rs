@@ -1,21 +1,85 @@
+mod account;
+mod admin;
 mod osuvs;
+mod presence;
 mod utils;
 
 use std::sync::Arc;
 
+use account::{Link, Unlink};
+use admin::Permissions;
+use presence::{Online, Presence};
 use twilight_model::application::{command::Command, interaction::ApplicationCommand};
 use utils::{Ping, Roll};
 
 use crate::{
     commands::osuvs::OsuVS,
     context::Context,
+    database::PermissionTarget,
     error::{BotResult, Error},
-    utils::ApplicationCommandExt,
+    utils::{ApplicationCommandExt, MessageBuilder, OWNER_USER_ID},
 };
 
+// Permissions
+pub const PERMISSION_ADMIN: &str = "admin";
+pub const PERMISSION_OSUVS_ADMIN: &str = "osuvs_admin";
+pub const PERMISSIONS: [&str; 2] = [PERMISSION_ADMIN, PERMISSION_OSUVS_ADMIN];
+
 pub fn twilight_commands() -> Vec<Command> {
-    // vec![Ping::define(), Roll::define(), OsuVS::define()]
-    vec![Ping::define(), Roll::define()]
+    vec![
+        Ping::define(),
+        Roll::define(),
+        OsuVS::define(),
+        Permissions::define(),
+        Link::define(),
+        Unlink::define(),
+        Online::define(),
+        Presence::define(),
+    ]
+}
+
+/// Check whether the command author was granted the permission, either directly
+/// or through one of their roles. The `admin` permission includes all others.
+pub async fn check_permission(
+    ctx: &Context,
+    command: &ApplicationCommand,
+    permission: &str,
+) -> BotResult<bool> {
+    let user_id = command.user_id()?;
+
+    if user_id.0 == OWNER_USER_ID {
+        return Ok(true);
+    }
+
+    let mut targets = ctx.database.get_permission_targets(permission).await?;
+
+    if permission != PERMISSION_ADMIN {
+        let admins = ctx
+            .database
+            .get_permission_targets(PERMISSION_ADMIN)
+            .await?;
+        targets.extend(admins);
+    }
+
+    let roles = command
+        .member
+        .as_ref()
+        .map_or(&[][..], |member| member.roles.as_slice());
+
+    let permitted = targets.into_iter().any(|target| match target {
+        PermissionTarget::Role(role_id) => roles.contains(&role_id),
+        PermissionTarget::User(target_id) => target_id == user_id,
+    });
+
+    Ok(permitted)
+}
+
+pub async fn permission_denied(ctx: &Context, command: &ApplicationCommand) -> BotResult<()> {
+    let builder = MessageBuilder::new()
+        .error("You do not have permission to use this command!")
+        .ephemeral();
+
+    command.create_message(ctx, builder).await
 }
 
 fn log_slash(ctx: &Context, command: &ApplicationCommand, cmd_name: &str) {
@@ -46,7 +110,12 @@ pub async fn handle_interaction(ctx: Arc<Context>, command: ApplicationCommand)
     match name {
         Ping::NAME => Ping::run(ctx, command).await,
         Roll::NAME => Roll::run(ctx, command).await,
-        // OsuVS::NAME => OsuVS::run(ctx, command).await,
+        OsuVS::NAME => OsuVS::run(ctx, command).await,
+        Permissions::NAME => Permissions::run(ctx, command).await,
+        Link::NAME => Link::run(ctx, command).await,
+        Unlink::NAME => Unlink::run(ctx, command).await,
+        Online::NAME => Online::run(ctx, command).await,
+        Presence::NAME => Presence::run(ctx, command).await,
         _ => Err(Error::UnknownInteraction {
             command: Box::new(command),
         }),
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use rosu_v2::prelude::GameMode;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::{check_permission, permission_denied, PERMISSION_OSUVS_ADMIN},
+    context::Context,
+    database::OsuVSMapBounds,
+    error::BotResult,
+    utils::{osu::mode_name, ApplicationCommandExt, MessageBuilder},
+};
+
+pub struct BoundsArgs {
+    pub mode: GameMode,
+    pub min_stars: Option<String>,
+    pub max_stars: Option<String>,
+    pub min_length: Option<String>,
+    pub max_length: Option<String>,
+}
+
+impl BoundsArgs {
+    fn is_empty(&self) -> bool {
+        self.min_stars.is_none()
+            && self.max_stars.is_none()
+            && self.min_length.is_none()
+            && self.max_length.is_none()
+    }
+}
+
+pub async fn bounds(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: BoundsArgs,
+) -> BotResult<()> {
+    if !check_permission(&ctx, &command, PERMISSION_OSUVS_ADMIN).await? {
+        return permission_denied(&ctx, &command).await;
+    }
+
+    let mode = args.mode;
+
+    if args.is_empty() {
+        let bounds = ctx.database.get_osuvs_map_bounds(mode).await?;
+        let content = format!("Limits for {} OsuVS maps:\n{}", mode_name(mode), bounds);
+        let builder = MessageBuilder::new().embed(content);
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let bounds = match parse_bounds(&args) {
+        Ok(bounds) => bounds,
+        Err(content) => {
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    info!("Updating osuvs map bounds of {}...", mode_name(mode));
+    ctx.database.set_osuvs_map_bounds(mode, &bounds).await?;
+
+    let content = format!(
+        "Updated the limits for {} OsuVS maps:\n{}",
+        mode_name(mode),
+        bounds
+    );
+    let builder = MessageBuilder::new().embed(content);
+
+    command.create_message(&ctx, builder).await
+}
+
+fn parse_bounds(args: &BoundsArgs) -> Result<OsuVSMapBounds, String> {
+    let parse_stars = |stars: &str| {
+        stars
+            .trim_end_matches('★')
+            .parse::<f32>()
+            .ok()
+            .filter(|stars| stars.is_finite() && *stars >= 0.0)
+            .ok_or_else(|| format!("Failed to parse `{}` as star rating!", stars))
+    };
+
+    let bounds = OsuVSMapBounds {
+        min_stars: args.min_stars.as_deref().map(parse_stars).transpose()?,
+        max_stars: args.max_stars.as_deref().map(parse_stars).transpose()?,
+        min_length: args.min_length.as_deref().map(parse_length).transpose()?,
+        max_length: args.max_length.as_deref().map(parse_length).transpose()?,
+    };
+
+    if let (Some(min), Some(max)) = (bounds.min_stars, bounds.max_stars) {
+        if min > max {
+            return Err("The minimum star rating must not exceed the maximum!".to_owned());
+        }
+    }
+
+    if let (Some(min), Some(max)) = (bounds.min_length, bounds.max_length) {
+        if min > max {
+            return Err("The minimum length must not exceed the maximum!".to_owned());
+        }
+    }
+
+    Ok(bounds)
+}
+
+/// Parse a length either as `M:SS` or as seconds
+fn parse_length(length: &str) -> Result<u32, String> {
+    let secs = match length.split_once(':') {
+        Some((mins, secs)) => mins
+            .parse::<u32>()
+            .ok()
+            .zip(secs.parse::<u32>().ok().filter(|&secs| secs < 60))
+            .map(|(mins, secs)| mins * 60 + secs),
+        None => length.parse().ok(),
+    };
+
+    secs.ok_or_else(|| format!("Failed to parse `{}` as length, use `M:SS`!", length))
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::{check_permission, permission_denied, PERMISSION_OSUVS_ADMIN},
+    context::Context,
+    error::BotResult,
+    utils::{osu::mode_name, ApplicationCommandExt, MessageBuilder, OSUVS_DATE_FORMAT},
+};
+
+pub async fn cancel(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    map_id: Option<u32>,
+) -> BotResult<()> {
+    if !check_permission(&ctx, &command, PERMISSION_OSUVS_ADMIN).await? {
+        return permission_denied(&ctx, &command).await;
+    }
+
+    let osuvs_map = match map_id {
+        Some(map_id) => ctx.database.get_osuvs_map(map_id).await?,
+        None => {
+            let builder = MessageBuilder::new()
+                .error("Please specify a valid map url of a difficulty, not of a mapset!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let osuvs_map = match osuvs_map {
+        Some(osuvs_map) if osuvs_map.end_date > Utc::now() => osuvs_map,
+        Some(_) => {
+            let builder = MessageBuilder::new().error("This OsuVS round is already over!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+        None => {
+            let builder = MessageBuilder::new().error("This map is not part of the OsuVS queue!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    // Removing the round would also remove the scores that reference it
+    let scores = ctx.database.osuvs_score_count(osuvs_map.map_id).await?;
+
+    if scores > 0 {
+        let content = format!(
+            "This OsuVS round already has {} score{} and can not be cancelled!",
+            scores,
+            if scores == 1 { "" } else { "s" }
+        );
+        let builder = MessageBuilder::new().error(content);
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Cancelling osuvs map {}...", osuvs_map.map_id);
+    ctx.database.remove_osuvs_map(osuvs_map.map_id).await?;
+
+    let content = format!(
+        "Cancelled the {} OsuVS round starting {}.\n\
+        Use `/osuvs reschedule` to move the following rounds into its slot.",
+        mode_name(osuvs_map.mode),
+        osuvs_map.start_date.format(OSUVS_DATE_FORMAT)
+    );
+    let builder = MessageBuilder::new().embed(content);
+
+    command.create_message(&ctx, builder).await
+}
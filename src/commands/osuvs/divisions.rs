@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use rosu_v2::prelude::GameMode;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::{check_permission, permission_denied, PERMISSION_OSUVS_ADMIN},
+    context::Context,
+    database::{OsuVSDivisionMetric, OsuVSDivisions},
+    error::BotResult,
+    utils::{osu::mode_name, ApplicationCommandExt, MessageBuilder},
+};
+
+/// Standings of all divisions are posted in a single message which
+/// can't have more than 10 embeds
+const MAX_DIVISIONS: usize = 5;
+
+pub struct DivisionsArgs {
+    pub mode: GameMode,
+    pub metric: Option<OsuVSDivisionMetric>,
+    pub thresholds: Option<String>,
+}
+
+pub async fn divisions(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: DivisionsArgs,
+) -> BotResult<()> {
+    if !check_permission(&ctx, &command, PERMISSION_OSUVS_ADMIN).await? {
+        return permission_denied(&ctx, &command).await;
+    }
+
+    let mode = args.mode;
+    let config = ctx.database.get_osuvs_division_config(mode).await?;
+
+    let thresholds = match args.thresholds {
+        Some(thresholds) => thresholds,
+        None => {
+            let content = format!(
+                "Divisions of new {} OsuVS rounds:\n{}",
+                mode_name(mode),
+                config
+            );
+            let builder = MessageBuilder::new().embed(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let metric = args.metric.unwrap_or(config.metric);
+
+    let divisions = match parse_thresholds(&thresholds) {
+        Ok(thresholds) => OsuVSDivisions::new(metric, thresholds),
+        Err(content) => {
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    info!("Updating osuvs divisions of {}...", mode_name(mode));
+    ctx.database
+        .set_osuvs_division_config(mode, &divisions)
+        .await?;
+
+    let content = format!(
+        "Updated the divisions of {} OsuVS rounds, they apply from the next round on:\n{}",
+        mode_name(mode),
+        divisions
+    );
+    let builder = MessageBuilder::new().embed(content);
+
+    command.create_message(&ctx, builder).await
+}
+
+/// Parse comma separated thresholds, `none` removes all divisions
+fn parse_thresholds(thresholds: &str) -> Result<Vec<u32>, String> {
+    if thresholds.eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    let thresholds = thresholds
+        .split(',')
+        .map(|threshold| {
+            let threshold = threshold
+                .trim()
+                .trim_start_matches('#')
+                .trim_end_matches("pp");
+
+            threshold
+                .replace('_', "")
+                .parse::<u32>()
+                .ok()
+                .filter(|&threshold| threshold > 0)
+                .ok_or_else(|| format!("Failed to parse `{}` as threshold!", threshold))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if thresholds.len() >= MAX_DIVISIONS {
+        return Err(format!("There can be at most {} divisions!", MAX_DIVISIONS));
+    }
+
+    Ok(thresholds)
+}
@@ -0,0 +1,253 @@
+use std::{fmt::Write, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use rosu_v2::prelude::GameMode;
+use serde_derive::Serialize;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    database::OsuVSMap,
+    error::BotResult,
+    utils::{
+        osuvs::{calculate_pp, score_username},
+        ApplicationCommandExt, MessageBuilder,
+    },
+};
+
+use super::results::find_round;
+
+const MODES: [GameMode; 4] = [GameMode::STD, GameMode::TKO, GameMode::CTB, GameMode::MNA];
+
+#[derive(Copy, Clone)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+pub struct ExportArgs {
+    pub round: Option<String>,
+    pub season: Option<String>,
+    pub mode: Option<GameMode>,
+    pub format: ExportFormat,
+}
+
+#[derive(Serialize)]
+struct ExportRow {
+    map_id: u32,
+    mode: String,
+    placement: usize,
+    user_id: u32,
+    username: String,
+    score: u32,
+    accuracy: f32,
+    mods: String,
+    max_combo: u32,
+    count_geki: u32,
+    count_300: u32,
+    count_katu: u32,
+    count_100: u32,
+    count_50: u32,
+    count_miss: u32,
+    pp: f32,
+    created_at: DateTime<Utc>,
+}
+
+pub async fn export(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: ExportArgs,
+) -> BotResult<()> {
+    let ExportArgs {
+        round,
+        season,
+        mode,
+        format,
+    } = args;
+
+    let (osuvs_maps, is_season) = match (round, season) {
+        (Some(round), None) => match find_round(&ctx, &round, mode).await {
+            Ok(osuvs_map) => (vec![osuvs_map], false),
+            Err(content) => {
+                let builder = MessageBuilder::new().error(content);
+
+                return command.create_message(&ctx, builder).await;
+            }
+        },
+        (None, Some(season)) => {
+            let season = match ctx.database.get_osuvs_season(&season).await? {
+                Some(season) => season,
+                None => {
+                    let builder = MessageBuilder::new().error("Could not find that OsuVS season!");
+
+                    return command.create_message(&ctx, builder).await;
+                }
+            };
+
+            let modes = match mode {
+                Some(mode) => vec![mode],
+                None => MODES.to_vec(),
+            };
+
+            let mut osuvs_maps = Vec::new();
+            let now = Utc::now();
+
+            for mode in modes {
+                let maps = ctx
+                    .database
+                    .get_season_osuvs_maps(&season, mode, now)
+                    .await?;
+
+                osuvs_maps.extend(maps);
+            }
+
+            (osuvs_maps, true)
+        }
+        _ => {
+            let content = "Please specify either a round or a season to export!";
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    info!("Exporting scores of {} osuvs rounds...", osuvs_maps.len());
+
+    // Calculating the pp of all scores might take a while
+    command.start_thinking(&ctx).await?;
+
+    let mut rows = Vec::new();
+
+    for osuvs_map in osuvs_maps.iter() {
+        rows.extend(export_rows(&ctx, osuvs_map).await?);
+    }
+
+    if rows.is_empty() {
+        let builder = MessageBuilder::new().error("No scores were submitted for these rounds!");
+
+        return command.update_message(&ctx, builder).await;
+    }
+
+    let data = match format {
+        ExportFormat::Csv => to_csv(&rows).into_bytes(),
+        ExportFormat::Json => serde_json::to_vec_pretty(&rows)?,
+    };
+
+    let filename = match (format, is_season) {
+        (ExportFormat::Csv, false) => "osuvs_round.csv",
+        (ExportFormat::Csv, true) => "osuvs_season.csv",
+        (ExportFormat::Json, false) => "osuvs_round.json",
+        (ExportFormat::Json, true) => "osuvs_season.json",
+    };
+
+    let content = format!(
+        "Exported {} scores of {} OsuVS round{}",
+        rows.len(),
+        osuvs_maps.len(),
+        if osuvs_maps.len() == 1 { "" } else { "s" }
+    );
+
+    let builder = MessageBuilder::new().content(content).file(filename, &data);
+
+    command.update_message(&ctx, builder).await
+}
+
+async fn export_rows(ctx: &Context, osuvs_map: &OsuVSMap) -> BotResult<Vec<ExportRow>> {
+    let mut highscores = ctx
+        .database
+        .get_osuvs_highscores(osuvs_map.map_id, &osuvs_map.rules, usize::MAX)
+        .await?;
+
+    if highscores.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    calculate_pp(
+        ctx,
+        osuvs_map,
+        highscores.iter_mut().map(|(_, score)| score),
+    )
+    .await?;
+
+    let rows = highscores
+        .into_iter()
+        .zip(1..)
+        .map(|((user_id, score), placement)| {
+            let stats = &score.statistics;
+
+            ExportRow {
+                map_id: osuvs_map.map_id,
+                mode: osuvs_map.mode.to_string(),
+                placement,
+                user_id,
+                username: score_username(user_id, &score),
+                score: score.score,
+                accuracy: score.accuracy,
+                mods: score.mods.to_string(),
+                max_combo: score.max_combo,
+                count_geki: stats.count_geki,
+                count_300: stats.count_300,
+                count_katu: stats.count_katu,
+                count_100: stats.count_100,
+                count_50: stats.count_50,
+                count_miss: stats.count_miss,
+                pp: score.pp.unwrap_or(0.0),
+                created_at: score.created_at,
+            }
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+fn to_csv(rows: &[ExportRow]) -> String {
+    let mut csv = String::from(
+        "map_id,mode,placement,user_id,username,score,accuracy,mods,max_combo,\
+        count_geki,count_300,count_katu,count_100,count_50,count_miss,pp,created_at\n",
+    );
+
+    for row in rows {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            row.map_id,
+            row.mode,
+            row.placement,
+            row.user_id,
+            csv_field(&row.username),
+            row.score,
+            row.accuracy,
+            row.mods,
+            row.max_combo,
+            row.count_geki,
+            row.count_300,
+            row.count_katu,
+            row.count_100,
+            row.count_50,
+            row.count_miss,
+            row.pp,
+            row.created_at.to_rfc3339(),
+        );
+    }
+
+    csv
+}
+
+/// Quote the field if it contains characters that would break the csv
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
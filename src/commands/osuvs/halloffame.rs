@@ -0,0 +1,107 @@
+use std::{fmt::Write, sync::Arc};
+
+use rosu_v2::prelude::GameMode;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    database::HallOfFameEntry,
+    error::BotResult,
+    utils::{
+        osu::mode_name,
+        pagination::{page_buttons, ComponentStream, Pages},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, MessageComponentExt,
+        EMOTE_MEDALS, OSU_BASE,
+    },
+};
+
+const ENTRIES_PER_PAGE: usize = 10;
+
+pub async fn halloffame(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    mode: Option<GameMode>,
+) -> BotResult<()> {
+    let mode = mode.unwrap_or(GameMode::STD);
+    let entries = ctx.database.get_osuvs_hall_of_fame(mode).await?;
+    let rounds = ctx.database.get_finished_osuvs_round_count(mode).await?;
+
+    if entries.is_empty() {
+        let content = format!("No {} OsuVS round has been won yet!", mode_name(mode));
+        let builder = MessageBuilder::new().error(content);
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Displaying osuvs hall of fame...");
+    let mut pages = Pages::new(ENTRIES_PER_PAGE, entries.len());
+    let embed = halloffame_embed(mode, rounds, &entries, &pages);
+
+    if pages.last_page() == 1 {
+        return command.create_message(&ctx, embed).await;
+    }
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, command.user_id()?);
+
+    let components = [page_buttons(&pages)];
+    let builder = MessageBuilder::new().embed(embed).components(&components);
+    command.create_message(&ctx, builder).await?;
+
+    while let Some(component) = stream.next().await {
+        if !pages.update(&component.data.custom_id) {
+            continue;
+        }
+
+        let embed = halloffame_embed(mode, rounds, &entries, &pages);
+        let components = [page_buttons(&pages)];
+        let builder = MessageBuilder::new().embed(embed).components(&components);
+        component.update_message(&ctx, builder).await?;
+    }
+
+    // Timed out, remove the buttons
+    let embed = halloffame_embed(mode, rounds, &entries, &pages);
+    command.update_message(&ctx, embed).await
+}
+
+fn halloffame_embed(
+    mode: GameMode,
+    rounds: usize,
+    entries: &[HallOfFameEntry],
+    pages: &Pages,
+) -> EmbedBuilder {
+    let mut description = String::new();
+    let page = entries.iter().skip(pages.index).take(pages.per_page);
+
+    for (entry, i) in page.zip(pages.index..) {
+        let prefix = match EMOTE_MEDALS.get(i) {
+            Some(medal) => (*medal).to_owned(),
+            None => format!("**{}.**", i + 1),
+        };
+
+        let _ = writeln!(
+            description,
+            "{} [{}]({}users/{}): **{}** win{} • {} podium{} • {} round{}",
+            prefix,
+            entry.username,
+            OSU_BASE,
+            entry.user_id,
+            entry.wins,
+            if entry.wins == 1 { "" } else { "s" },
+            entry.podiums,
+            if entry.podiums == 1 { "" } else { "s" },
+            entry.rounds,
+            if entry.rounds == 1 { "" } else { "s" },
+        );
+    }
+
+    let footer = format!("{} • {} finished rounds", pages.footer_text(), rounds);
+
+    EmbedBuilder::new()
+        .author(Author::new(format!(
+            "{} OsuVS Hall of Fame",
+            mode_name(mode)
+        )))
+        .description(description)
+        .footer(Footer::new(footer))
+}
@@ -0,0 +1,132 @@
+use std::{fmt::Write, sync::Arc};
+
+use futures::future::try_join_all;
+use rosu_v2::prelude::GameMode;
+use twilight_model::{application::interaction::ApplicationCommand, channel::embed::Embed};
+
+use crate::{
+    context::Context,
+    database::OsuVSMap,
+    error::BotResult,
+    utils::{
+        osu::{map_to_string, mode_name},
+        osuvs::metric_value,
+        pagination::{page_buttons, ComponentStream, Pages},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, MessageComponentExt,
+        EMOTE_MEDALS, OSU_BASE,
+    },
+};
+
+const ROUNDS_PER_PAGE: usize = 5;
+
+pub async fn history(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    mode: Option<GameMode>,
+) -> BotResult<()> {
+    let mut osuvs_maps = ctx.database.get_past_osuvs_maps().await?;
+
+    if let Some(mode) = mode {
+        osuvs_maps.retain(|osuvs_map| osuvs_map.mode == mode);
+    }
+
+    if osuvs_maps.is_empty() {
+        let builder = MessageBuilder::new().error("There are no finished OsuVS rounds yet!");
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Displaying osuvs history...");
+    let mut pages = Pages::new(ROUNDS_PER_PAGE, osuvs_maps.len());
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, command.user_id()?);
+
+    // Requesting the maps might take a while
+    command.start_thinking(&ctx).await?;
+
+    let mut embed = history_embed(&ctx, &osuvs_maps, &pages).await?;
+    let components = [page_buttons(&pages)];
+    let builder = MessageBuilder::new()
+        .embed(embed.clone())
+        .components(&components);
+    command.update_message(&ctx, builder).await?;
+
+    while let Some(component) = stream.next().await {
+        if !pages.update(&component.data.custom_id) {
+            continue;
+        }
+
+        embed = history_embed(&ctx, &osuvs_maps, &pages).await?;
+        let components = [page_buttons(&pages)];
+        let builder = MessageBuilder::new()
+            .embed(embed.clone())
+            .components(&components);
+        component.update_message(&ctx, builder).await?;
+    }
+
+    // Timed out, remove the buttons
+    command.update_message(&ctx, embed).await
+}
+
+async fn history_embed(ctx: &Context, osuvs_maps: &[OsuVSMap], pages: &Pages) -> BotResult<Embed> {
+    let entries = &osuvs_maps[pages.index..osuvs_maps.len().min(pages.index + pages.per_page)];
+
+    let map_futs = entries
+        .iter()
+        .map(|osuvs_map| ctx.osu.beatmap().map_id(osuvs_map.map_id));
+    let maps = try_join_all(map_futs).await?;
+
+    let winner_futs = entries.iter().map(|osuvs_map| {
+        ctx.database
+            .get_osuvs_highscores(osuvs_map.map_id, &osuvs_map.rules, 1)
+    });
+    let winners = try_join_all(winner_futs).await?;
+
+    let mut description = String::new();
+    let rounds = entries.iter().zip(maps).zip(winners);
+
+    // Oldest round is number one
+    for (((osuvs_map, map), winner), i) in rounds.zip(pages.index..) {
+        let _ = writeln!(
+            description,
+            "**#{}** [{}]({}b/{}) `{}`\n\
+            - <t:{}:d> until <t:{}:d>",
+            osuvs_maps.len() - i,
+            map_to_string(&map),
+            OSU_BASE,
+            osuvs_map.map_id,
+            mode_name(osuvs_map.mode),
+            osuvs_map.start_date.timestamp(),
+            osuvs_map.end_date.timestamp(),
+        );
+
+        match winner.first() {
+            Some((user_id, score)) => {
+                let username = score
+                    .user
+                    .as_ref()
+                    .map_or_else(|| user_id.to_string(), |user| user.username.clone());
+
+                let _ = writeln!(
+                    description,
+                    "- {} [{}]({}users/{}) with {}",
+                    EMOTE_MEDALS[0],
+                    username,
+                    OSU_BASE,
+                    user_id,
+                    metric_value(osuvs_map.rules.metric, score)
+                );
+            }
+            None => description.push_str("- No scores were submitted\n"),
+        }
+    }
+
+    let embed = EmbedBuilder::new()
+        .author(Author::new("Past OsuVS rounds"))
+        .description(description)
+        .footer(Footer::new(pages.footer_text()))
+        .build();
+
+    Ok(embed)
+}
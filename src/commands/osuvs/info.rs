@@ -1,32 +1,44 @@
 use std::sync::Arc;
 
 use chrono::Duration;
+use rosu_v2::prelude::GameMode;
 use twilight_model::application::interaction::ApplicationCommand;
 
 use crate::{
     context::Context,
     error::BotResult,
     utils::{
-        datetime::sec_to_minsec, numbers::round, osu::map_to_string, ApplicationCommandExt, Author,
-        EmbedBuilder, Footer, MessageBuilder, OSUVS_DATE_FORMAT, OSU_BASE,
+        datetime::sec_to_minsec,
+        numbers::round,
+        osu::{map_to_string, mode_name},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, OSUVS_DATE_FORMAT, OSU_BASE,
     },
 };
 
-pub async fn info(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+use super::curr_osuvs_map;
+
+pub async fn info(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    mode: Option<GameMode>,
+) -> BotResult<()> {
     info!("Displaying current osuvs info...");
-    match ctx.database.get_curr_osuvs_map().await {
-        Some((map_id, start_date, end_date)) => {
+    match curr_osuvs_map(&ctx, &command, mode).await? {
+        Some(osuvs_map) => {
+            let map_id = osuvs_map.map_id;
             //? Send message to user in case of error?
             let map = ctx.osu.beatmap().map_id(map_id).await?;
             let title = map_to_string(&map);
             let url = format!("{}b/{}", OSU_BASE, map_id);
             let author = Author::new(format!(
-                "This OsuVS started on {}",
-                start_date.format(OSUVS_DATE_FORMAT)
+                "This {} OsuVS started on {}",
+                mode_name(osuvs_map.mode),
+                osuvs_map.start_date.format(OSUVS_DATE_FORMAT)
             ));
             let description = format!(
                 "Stars: `{}★` Length: `{}` (`{}`) Combo: `{}x`\n\
-                CS: `{}` HP: `{}` OD: `{}` AR: `{}`",
+                CS: `{}` HP: `{}` OD: `{}` AR: `{}`\n\
+                {}",
                 round(map.stars),
                 sec_to_minsec(map.seconds_total),
                 sec_to_minsec(map.seconds_drain),
@@ -34,7 +46,8 @@ pub async fn info(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<(
                 round(map.cs),
                 round(map.hp),
                 round(map.od),
-                round(map.ar)
+                round(map.ar),
+                osuvs_map.rules
             );
             let image = format!(
                 "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
@@ -42,7 +55,7 @@ pub async fn info(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<(
             );
             let footer = Footer::new(format!(
                 "You can submit plays until {}",
-                (end_date - Duration::minutes(5)).format(OSUVS_DATE_FORMAT)
+                (osuvs_map.end_date - Duration::minutes(5)).format(OSUVS_DATE_FORMAT)
             ));
             let builder = EmbedBuilder::new()
                 .title(title)
@@ -54,10 +67,6 @@ pub async fn info(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<(
 
             command.create_message(&ctx, builder).await
         }
-        None => {
-            let builder = MessageBuilder::new().error("There is currently no ongoing OsuVS!");
-
-            command.create_message(&ctx, builder).await
-        }
+        None => Ok(()),
     }
 }
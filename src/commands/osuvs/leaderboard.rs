@@ -1,128 +1,193 @@
-use std::fmt::Write;
-use std::sync::Arc;
+use std::{fmt::Write, str::FromStr, sync::Arc};
 
-use futures::future::try_join_all;
-use hashbrown::HashMap;
-use rosu_pp::{osu::OsuPerformanceAttributes, Beatmap, OsuPP};
-use rosu_v2::prelude::{GameMode, GameMods};
-use twilight_model::application::interaction::ApplicationCommand;
+use rosu_v2::prelude::{Beatmap, GameMode, GameMods};
+use twilight_model::{
+    application::{
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+        interaction::ApplicationCommand,
+    },
+    channel::embed::Embed,
+};
 
 use crate::{
     context::Context,
+    database::OsuVSMap,
     error::BotResult,
     utils::{
-        numbers::{round, with_comma_uint},
-        osu::{map_to_string, prepare_beatmap_file},
-        ApplicationCommandExt, Author, EmbedBuilder, MessageBuilder, EMOTE_RANKS, OSU_BASE,
+        osu::{map_to_string, mode_name},
+        osuvs::{leaderboard_page, osuvs_leaderboard, OsuVSEntry},
+        pagination::{page_buttons, ComponentStream, Pages},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, MessageComponentExt,
+        OSU_BASE,
     },
 };
 
-pub async fn leaderboard(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
-    info!("Displaying current osuvs info...");
-    match ctx.database.get_curr_osuvs_map().await {
-        Some((map_id, _, _)) => {
-            //? Send message to user in case of error?
-            let map = ctx.osu.beatmap().map_id(map_id).await?;
-            //? Send message to user in case of error?
-            //? Maybe put this in get_osuvs_highscores function itself?
-            let highscores: Vec<_> = ctx.database.get_osuvs_highscores(10).await?;
-            let mut users: HashMap<_, _> = {
-                let user_reqs = highscores
-                    .iter()
-                    .map(|(user_id, _)| ctx.osu.user(*user_id).mode(GameMode::STD));
-
-                match try_join_all(user_reqs).await {
-                    Ok(options) => options.into_iter().map(|u| (u.user_id, u)).collect(),
-                    Err(why) => {
-                        unwind_error!(
-                            error,
-                            why,
-                            "Error when retrieving users for osuvs highscores: {}"
-                        );
-                        HashMap::new()
-                    }
+use super::{curr_osuvs_map, retain_division};
+
+const ENTRIES_PER_PAGE: usize = 10;
+
+const BUTTON_SHOW_ME: &str = "leaderboard_show_me";
+
+pub async fn leaderboard(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    mode: Option<GameMode>,
+    mods: Option<String>,
+    division: Option<u8>,
+) -> BotResult<()> {
+    let mods = match mods.as_deref().map(GameMods::from_str) {
+        Some(Ok(mods)) => Some(mods),
+        Some(Err(_)) => {
+            let content = format!("Failed to parse `{}` as mods!", mods.unwrap());
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+        None => None,
+    };
+
+    let osuvs_map = match curr_osuvs_map(&ctx, &command, mode).await? {
+        Some(osuvs_map) => osuvs_map,
+        None => return Ok(()),
+    };
+
+    info!("Displaying current osuvs leaderboard...");
+    let user_id = command.user_id()?;
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, user_id);
+
+    // Calculating the pp of all scores might take a while
+    command.start_thinking(&ctx).await?;
+
+    //? Send message to user in case of error?
+    let map = ctx.osu.beatmap().map_id(osuvs_map.map_id).await?;
+    //? Send message to user in case of error?
+    let mut entries = osuvs_leaderboard(&ctx, &osuvs_map, usize::MAX).await?;
+
+    if let Some(mods) = mods {
+        entries.retain(|entry| entry.score.mods == mods);
+    }
+
+    let division = match division {
+        Some(division) => {
+            match retain_division(&ctx, osuvs_map.map_id, division, &mut entries).await? {
+                Ok(name) => Some(name),
+                Err(content) => {
+                    let builder = MessageBuilder::new().error(content);
+
+                    return command.update_message(&ctx, builder).await;
                 }
-            };
-            let highscores: Vec<_> = highscores
-                .into_iter()
-                .map(|(user_id, score)| (users.remove(&user_id).unwrap(), score))
-                .collect();
-
-            let title = map_to_string(&map);
-            let url = format!("{}b/{}", OSU_BASE, map_id);
-            let author = Author::new("Current OsuVS Leaderboard");
-
-            if highscores.is_empty() {
-                let description = "No scores have been submitted yet! This is very sad :(";
-                let builder = EmbedBuilder::new()
-                    .title(title)
-                    .url(url)
-                    .author(author)
-                    .description(description);
-                return command.create_message(&ctx, builder).await;
             }
-            let map_path = prepare_beatmap_file(map_id).await?;
-            let pp_map = Beatmap::from_path(map_path).await?;
-            let mut description = String::new();
-            let mut attr_values: HashMap<GameMods, OsuPerformanceAttributes> = HashMap::new();
-            let thumbnail = format!("https://a.ppy.sh/{}", highscores[0].0.user_id);
-
-            for ((user, score), i) in highscores.into_iter().zip(1..) {
-                let attributes = match attr_values.get(&score.mods) {
-                    Some(attrs) => attrs.to_owned(),
-                    None => OsuPP::new(&pp_map).mods(score.mods.bits()).calculate(),
-                };
-
-                let max_pp = attributes.pp();
-                let pp = OsuPP::new(&pp_map)
-                    .attributes(attributes.difficulty.clone())
-                    .mods(score.mods.bits())
-                    .combo(score.max_combo as usize)
-                    .misses(score.statistics.count_miss as usize)
-                    .n300(score.statistics.count_300 as usize)
-                    .n100(score.statistics.count_100 as usize)
-                    .n50(score.statistics.count_50 as usize)
-                    .calculate()
-                    .pp();
-                attr_values.insert(score.mods, attributes);
-
-                let _ = writeln!(
-                    description,
-                    "**{}.** {} [{}]({}users/{}): {} [ **{}x**/{}x ] **+{}**\n\
-                - **{}**/{}PP - {}% - <t:{}:R>",
-                    i,
-                    EMOTE_RANKS[&score.grade],
-                    user.username,
-                    OSU_BASE,
-                    user.user_id,
-                    with_comma_uint(score.score),
-                    score.max_combo,
-                    map.max_combo.unwrap_or(0),
-                    score.mods,
-                    round(pp as f32),
-                    round(max_pp as f32),
-                    round(score.accuracy),
-                    &score.created_at.timestamp()
-                );
-            }
-            let image = format!(
-                "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
-                map.mapset_id
-            );
-            let builder = EmbedBuilder::new()
-                .title(title)
-                .url(url)
-                .thumbnail(thumbnail)
-                .author(author)
-                .description(description)
-                .image(image);
-
-            command.create_message(&ctx, builder).await
         }
-        None => {
-            let builder = MessageBuilder::new().error("There is currently no ongoing OsuVS!");
+        None => None,
+    };
+
+    let author = leaderboard_author(&osuvs_map, division.as_deref());
+
+    if entries.is_empty() {
+        let description = match mods {
+            Some(mods) => format!("No scores with `{}` have been submitted yet!", mods),
+            None => "No scores have been submitted yet! This is very sad :(".to_owned(),
+        };
+
+        let embed = EmbedBuilder::new()
+            .title(map_to_string(&map))
+            .url(format!("{}b/{}", OSU_BASE, osuvs_map.map_id))
+            .author(Author::new(author))
+            .description(description);
+
+        return command.update_message(&ctx, embed).await;
+    }
+
+    let osu_id = ctx.database.get_manual_link(user_id).await?;
+    let own_idx = osu_id.and_then(|id| entries.iter().position(|entry| entry.user_id == id));
+
+    let mut pages = Pages::new(ENTRIES_PER_PAGE, entries.len());
+    let embed = leaderboard_embed(&osuvs_map, &map, &author, &entries, &pages, mods, osu_id);
+
+    if pages.last_page() == 1 {
+        return command.update_message(&ctx, embed).await;
+    }
+
+    let components = leaderboard_components(&pages, own_idx);
+    let builder = MessageBuilder::new().embed(embed).components(&components);
+    command.update_message(&ctx, builder).await?;
 
-            command.create_message(&ctx, builder).await
+    while let Some(component) = stream.next().await {
+        match (component.data.custom_id.as_str(), own_idx) {
+            (BUTTON_SHOW_ME, Some(idx)) => pages.jump_to(idx),
+            (custom_id, _) if pages.update(custom_id) => {}
+            _ => continue,
         }
+
+        let embed = leaderboard_embed(&osuvs_map, &map, &author, &entries, &pages, mods, osu_id);
+        let components = leaderboard_components(&pages, own_idx);
+        let builder = MessageBuilder::new().embed(embed).components(&components);
+        component.update_message(&ctx, builder).await?;
     }
+
+    // Timed out, remove the buttons
+    let embed = leaderboard_embed(&osuvs_map, &map, &author, &entries, &pages, mods, osu_id);
+    command.update_message(&ctx, embed).await
+}
+
+fn leaderboard_author(osuvs_map: &OsuVSMap, division: Option<&str>) -> String {
+    let mut author = format!("Current {} OsuVS Leaderboard", mode_name(osuvs_map.mode));
+
+    if let Some(division) = division {
+        let _ = write!(author, " • {}", division);
+    }
+
+    author
+}
+
+fn leaderboard_embed(
+    osuvs_map: &OsuVSMap,
+    map: &Beatmap,
+    author: &str,
+    entries: &[OsuVSEntry],
+    pages: &Pages,
+    mods: Option<GameMods>,
+    osu_id: Option<u32>,
+) -> Embed {
+    let max_combo = map.max_combo.unwrap_or(0);
+    let description = leaderboard_page(entries, pages, max_combo, osu_id);
+
+    let footer = match mods {
+        Some(mods) => format!("{} • Only scores with {}", pages.footer_text(), mods),
+        None => pages.footer_text(),
+    };
+
+    EmbedBuilder::new()
+        .title(map_to_string(map))
+        .url(format!("{}b/{}", OSU_BASE, osuvs_map.map_id))
+        .thumbnail(format!("https://a.ppy.sh/{}", entries[0].user_id))
+        .author(Author::new(author))
+        .description(description)
+        .image(format!(
+            "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
+            map.mapset_id
+        ))
+        .footer(Footer::new(footer))
+        .build()
+}
+
+/// Page buttons alongside a button to jump to the page of the caller
+/// which is disabled if they're not on the leaderboard
+fn leaderboard_components(pages: &Pages, own_idx: Option<usize>) -> [Component; 2] {
+    let show_me = Button {
+        custom_id: Some(BUTTON_SHOW_ME.to_owned()),
+        disabled: !matches!(own_idx, Some(idx) if idx / pages.per_page != pages.index / pages.per_page),
+        emoji: None,
+        label: Some("Show me".to_owned()),
+        style: ButtonStyle::Primary,
+        url: None,
+    };
+
+    let show_me = Component::ActionRow(ActionRow {
+        components: vec![Component::Button(show_me)],
+    });
+
+    [page_buttons(pages), show_me]
 }
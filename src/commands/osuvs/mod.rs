@@ -1,20 +1,63 @@
+mod bounds;
+mod cancel;
+mod divisions;
+mod export;
+mod halloffame;
+mod history;
 mod info;
 mod leaderboard;
+mod notifications;
+mod profile;
+mod queue;
 mod request;
 mod request_list;
+mod reschedule;
+mod results;
+mod season;
+mod standings;
 mod start;
+mod submit;
+
+use bounds::BoundsArgs;
+use divisions::DivisionsArgs;
+use export::{ExportArgs, ExportFormat};
+use reschedule::RescheduleArgs;
+use season::SeasonArgs;
+use start::StartArgs;
 
 use std::sync::Arc;
 
-use twilight_model::application::{
-    command::{ChoiceCommandOptionData, CommandOption, OptionsCommandOptionData},
-    interaction::{
-        application_command::{CommandData, CommandDataOption},
-        ApplicationCommand,
+use chrono::{DateTime, Duration, Utc};
+use rosu_v2::prelude::{Beatmap, GameMode, GameMods, RankStatus};
+use twilight_model::{
+    application::{
+        command::{
+            BaseCommandOptionData, ChoiceCommandOptionData, CommandOption, CommandOptionChoice,
+            OptionsCommandOptionData,
+        },
+        interaction::{
+            application_command::{CommandData, CommandDataOption},
+            ApplicationCommand,
+        },
     },
+    id::UserId,
 };
 
-use crate::{context::Context, error::BotResult, utils::osu::get_osu_map_id};
+use crate::{
+    context::Context,
+    database::{OsuVSDivisionMetric, OsuVSMap, OsuVSMetric, OsuVSRules},
+    error::BotResult,
+    utils::{
+        datetime::parse_utc_date,
+        osu::{get_osu_map_id, mode_name, parse_mode, PpCalculator},
+        osuvs::OsuVSEntry,
+        ApplicationCommandExt, MessageBuilder,
+    },
+};
+
+/// Duration of an osuvs round if none is specified
+const DEFAULT_DURATION_DAYS: i64 = 7;
+const MAX_DURATION_DAYS: i64 = 28;
 
 #[command]
 #[args = "OsuVSArgs"]
@@ -23,11 +66,43 @@ use crate::{context::Context, error::BotResult, utils::osu::get_osu_map_id};
 pub struct OsuVS;
 
 pub enum OsuVSArgs {
-    Info,
-    Leaderboard,
+    Bounds(BoundsArgs),
+    Cancel(Option<u32>),
+    Divisions(DivisionsArgs),
+    Export(ExportArgs),
+    HallOfFame(Option<GameMode>),
+    History(Option<GameMode>),
+    Info(Option<GameMode>),
+    Leaderboard {
+        mode: Option<GameMode>,
+        mods: Option<String>,
+        division: Option<u8>,
+    },
+    Notifications(bool),
+    Profile {
+        user: Option<UserId>,
+        mode: Option<GameMode>,
+    },
+    Queue(Option<GameMode>),
     RequestList,
     Request(Option<u32>),
-    Start(Option<u32>),
+    Reschedule(RescheduleArgs),
+    Results {
+        round: String,
+        mode: Option<GameMode>,
+        division: Option<u8>,
+    },
+    Season(SeasonArgs),
+    Standings {
+        season: Option<String>,
+        mode: Option<GameMode>,
+        division: Option<u8>,
+    },
+    Start(StartArgs),
+    Submit {
+        score_id: Option<u64>,
+        mode: Option<GameMode>,
+    },
 }
 
 impl OsuVSArgs {
@@ -35,11 +110,25 @@ impl OsuVSArgs {
         for option in data.options {
             if let CommandDataOption::SubCommand { name, options } = option {
                 match name.as_str() {
-                    "info" => return Ok(Self::Info),
-                    "leaderboard" => return Ok(Self::Leaderboard),
+                    "bounds" => return Self::parse_bounds_options(options),
+                    "cancel" => return Ok(Self::Cancel(Self::parse_map_option(options))),
+                    "divisions" => return Self::parse_divisions_options(options),
+                    "export" => return Self::parse_export_options(options),
+                    "halloffame" => return Ok(Self::HallOfFame(Self::parse_mode_option(options))),
+                    "history" => return Ok(Self::History(Self::parse_mode_option(options))),
+                    "info" => return Ok(Self::Info(Self::parse_mode_option(options))),
+                    "leaderboard" => return Self::parse_leaderboard_options(options),
+                    "notifications" => return Self::parse_notifications_options(options),
+                    "profile" => return Self::parse_profile_options(options),
+                    "queue" => return Ok(Self::Queue(Self::parse_mode_option(options))),
                     "requestlist" => return Ok(Self::RequestList),
-                    "request" => return Self::parse_request_options(options),
+                    "request" => return Ok(Self::Request(Self::parse_map_option(options))),
+                    "reschedule" => return Self::parse_reschedule_options(options),
+                    "results" => return Self::parse_results_options(options),
+                    "season" => return Self::parse_season_options(options),
+                    "standings" => return Self::parse_standings_options(options),
                     "start" => return Self::parse_start_options(options),
+                    "submit" => return Self::parse_submit_options(options),
                     _ => (),
                 }
             }
@@ -48,12 +137,12 @@ impl OsuVSArgs {
         unreachable!();
     }
 
-    fn parse_request_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+    #[allow(clippy::result_large_err)]
+    fn parse_notifications_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
         for option in options {
-            if let CommandDataOption::String { name, value } = option {
-                if name == "map" {
-                    let map_id = get_osu_map_id(&value);
-                    return Ok(Self::Request(map_id));
+            if let CommandDataOption::Boolean { name, value } = option {
+                if name == "enabled" {
+                    return Ok(Self::Notifications(value));
                 }
             }
         }
@@ -61,32 +150,564 @@ impl OsuVSArgs {
         unreachable!()
     }
 
+    #[allow(clippy::result_large_err)]
+    fn parse_bounds_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut mode = None;
+        let mut min_stars = None;
+        let mut max_stars = None;
+        let mut min_length = None;
+        let mut max_length = None;
+
+        for option in options {
+            if let CommandDataOption::String { name, value } = option {
+                match name.as_str() {
+                    "mode" => mode = parse_mode(&value),
+                    "min_stars" => min_stars = Some(value),
+                    "max_stars" => max_stars = Some(value),
+                    "min_length" => min_length = Some(value),
+                    "max_length" => max_length = Some(value),
+                    _ => (),
+                }
+            }
+        }
+
+        let args = BoundsArgs {
+            mode: mode.unwrap_or(GameMode::STD),
+            min_stars,
+            max_stars,
+            min_length,
+            max_length,
+        };
+
+        Ok(Self::Bounds(args))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_divisions_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut mode = None;
+        let mut metric = None;
+        let mut thresholds = None;
+
+        for option in options {
+            if let CommandDataOption::String { name, value } = option {
+                match name.as_str() {
+                    "mode" => mode = parse_mode(&value),
+                    "by" => metric = OsuVSDivisionMetric::from_name(&value),
+                    "thresholds" => thresholds = Some(value),
+                    _ => (),
+                }
+            }
+        }
+
+        let args = DivisionsArgs {
+            mode: mode.unwrap_or(GameMode::STD),
+            metric,
+            thresholds,
+        };
+
+        Ok(Self::Divisions(args))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_profile_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut user = None;
+        let mut mode = None;
+
+        for option in options {
+            if let CommandDataOption::String { name, value } = option {
+                match name.as_str() {
+                    "user" => user = value.parse().ok().map(UserId),
+                    "mode" => mode = parse_mode(&value),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Self::Profile { user, mode })
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_reschedule_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut map_id = None;
+        let mut start = None;
+        let mut duration = None;
+
+        for option in options {
+            match option {
+                CommandDataOption::String { name, value } => match name.as_str() {
+                    "map" => map_id = get_osu_map_id(&value),
+                    "start" => start = Some(value),
+                    _ => (),
+                },
+                CommandDataOption::Integer { name, value } if name == "duration" => {
+                    duration = Some(value)
+                }
+                _ => (),
+            }
+        }
+
+        let args = RescheduleArgs {
+            map_id,
+            start: start.unwrap(),
+            duration,
+        };
+
+        Ok(Self::Reschedule(args))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_export_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut round = None;
+        let mut season = None;
+        let mut mode = None;
+        let mut format = None;
+
+        for option in options {
+            if let CommandDataOption::String { name, value } = option {
+                match name.as_str() {
+                    "round" => round = Some(value),
+                    "season" => season = Some(value),
+                    "mode" => mode = parse_mode(&value),
+                    "format" => format = ExportFormat::parse(&value),
+                    _ => (),
+                }
+            }
+        }
+
+        let args = ExportArgs {
+            round,
+            season,
+            mode,
+            format: format.unwrap_or(ExportFormat::Csv),
+        };
+
+        Ok(Self::Export(args))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_leaderboard_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut mode = None;
+        let mut mods = None;
+        let mut division = None;
+
+        for option in options {
+            match option {
+                CommandDataOption::String { name, value } => match name.as_str() {
+                    "mode" => mode = parse_mode(&value),
+                    "mods" => mods = Some(value),
+                    _ => (),
+                },
+                CommandDataOption::Integer { name, value } if name == "division" => {
+                    division = Some(value.clamp(0, u8::MAX as i64) as u8)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self::Leaderboard {
+            mode,
+            mods,
+            division,
+        })
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_results_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut round = None;
+        let mut mode = None;
+        let mut division = None;
+
+        for option in options {
+            match option {
+                CommandDataOption::String { name, value } => match name.as_str() {
+                    "round" => round = Some(value),
+                    "mode" => mode = parse_mode(&value),
+                    _ => (),
+                },
+                CommandDataOption::Integer { name, value } if name == "division" => {
+                    division = Some(value.clamp(0, u8::MAX as i64) as u8)
+                }
+                _ => (),
+            }
+        }
+
+        let round = round.unwrap();
+
+        Ok(Self::Results {
+            round,
+            mode,
+            division,
+        })
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_season_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut name = None;
+        let mut start = None;
+        let mut end = None;
+        let mut points = None;
+        let mut participation = None;
+
+        for option in options {
+            match option {
+                CommandDataOption::String {
+                    name: option,
+                    value,
+                } => match option.as_str() {
+                    "name" => name = Some(value),
+                    "start" => start = Some(value),
+                    "end" => end = Some(value),
+                    "points" => points = Some(value),
+                    _ => (),
+                },
+                CommandDataOption::Integer {
+                    name: option,
+                    value,
+                } if option == "participation" => participation = Some(value),
+                _ => (),
+            }
+        }
+
+        let args = SeasonArgs {
+            name: name.unwrap(),
+            start: start.unwrap(),
+            end: end.unwrap(),
+            points,
+            participation,
+        };
+
+        Ok(Self::Season(args))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_standings_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut season = None;
+        let mut mode = None;
+        let mut division = None;
+
+        for option in options {
+            match option {
+                CommandDataOption::String { name, value } => match name.as_str() {
+                    "season" => season = Some(value),
+                    "mode" => mode = parse_mode(&value),
+                    _ => (),
+                },
+                CommandDataOption::Integer { name, value } if name == "division" => {
+                    division = Some(value.clamp(0, u8::MAX as i64) as u8)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self::Standings {
+            season,
+            mode,
+            division,
+        })
+    }
+
     fn parse_start_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut args = StartArgs::default();
+
+        for option in options {
+            match option {
+                CommandDataOption::String { name, value } => match name.as_str() {
+                    "map" => args.map_id = get_osu_map_id(&value),
+                    "mode" => args.mode = parse_mode(&value),
+                    "required_mods" => args.required_mods = Some(value),
+                    "forbidden_mods" => args.forbidden_mods = Some(value),
+                    "metric" => args.metric = OsuVSMetric::from_name(&value),
+                    "tiebreak" => args.tiebreak = OsuVSMetric::from_name(&value),
+                    "start" => args.start = Some(value),
+                    _ => (),
+                },
+                CommandDataOption::Integer { name, value } if name == "duration" => {
+                    args.duration = Some(value)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self::Start(args))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn parse_submit_options(options: Vec<CommandDataOption>) -> BotResult<Self> {
+        let mut score_id = None;
+        let mut mode = None;
+
         for option in options {
             if let CommandDataOption::String { name, value } = option {
-                if name == "map" {
-                    let map_id = get_osu_map_id(&value);
-                    return Ok(Self::Start(map_id));
+                match name.as_str() {
+                    // Either the id itself or a score url ending with the id
+                    "score" => {
+                        score_id = value
+                            .trim_end_matches('/')
+                            .rsplit('/')
+                            .next()
+                            .and_then(|id| id.parse().ok())
+                    }
+                    "mode" => mode = parse_mode(&value),
+                    _ => (),
                 }
             }
         }
 
-        unreachable!()
+        Ok(Self::Submit { score_id, mode })
+    }
+
+    fn parse_map_option(options: Vec<CommandDataOption>) -> Option<u32> {
+        options.into_iter().find_map(|option| match option {
+            CommandDataOption::String { name, value } if name == "map" => get_osu_map_id(&value),
+            _ => None,
+        })
+    }
+
+    fn parse_mode_option(options: Vec<CommandDataOption>) -> Option<GameMode> {
+        options.into_iter().find_map(|option| match option {
+            CommandDataOption::String { name, value } if name == "mode" => parse_mode(&value),
+            _ => None,
+        })
     }
 }
 
+fn mode_option(description: &str) -> CommandOption {
+    let choices = [GameMode::STD, GameMode::TKO, GameMode::CTB, GameMode::MNA]
+        .iter()
+        .map(|&mode| CommandOptionChoice::String {
+            name: mode_name(mode).to_owned(),
+            value: mode.to_string(),
+        })
+        .collect();
+
+    CommandOption::String(ChoiceCommandOptionData {
+        choices,
+        description: description.to_string(),
+        name: "mode".to_string(),
+        required: false,
+    })
+}
+
+fn metric_option(name: &str, description: &str) -> CommandOption {
+    let choices = OsuVSMetric::ALL
+        .iter()
+        .map(|metric| CommandOptionChoice::String {
+            name: metric.name().to_owned(),
+            value: metric.name().to_owned(),
+        })
+        .collect();
+
+    CommandOption::String(ChoiceCommandOptionData {
+        choices,
+        description: description.to_string(),
+        name: name.to_string(),
+        required: false,
+    })
+}
+
+fn map_option(description: &str) -> CommandOption {
+    CommandOption::String(ChoiceCommandOptionData {
+        choices: vec![],
+        description: description.to_string(),
+        name: "map".to_string(),
+        required: true,
+    })
+}
+
+fn start_date_option(description: &str, required: bool) -> CommandOption {
+    CommandOption::String(ChoiceCommandOptionData {
+        choices: vec![],
+        description: description.to_string(),
+        name: "start".to_string(),
+        required,
+    })
+}
+
+fn duration_option(description: &str) -> CommandOption {
+    CommandOption::Integer(ChoiceCommandOptionData {
+        choices: vec![],
+        description: description.to_string(),
+        name: "duration".to_string(),
+        required: false,
+    })
+}
+
+fn division_option(description: &str) -> CommandOption {
+    CommandOption::Integer(ChoiceCommandOptionData {
+        choices: vec![],
+        description: description.to_string(),
+        name: "division".to_string(),
+        required: false,
+    })
+}
+
+fn string_option(name: &str, description: &str) -> CommandOption {
+    CommandOption::String(ChoiceCommandOptionData {
+        choices: vec![],
+        description: description.to_string(),
+        name: name.to_string(),
+        required: false,
+    })
+}
+
 fn osuvs_options() -> Vec<CommandOption> {
+    let bounds = OptionsCommandOptionData {
+        description: "Show or set the limits for osuvs maps. Only admins can use this".to_string(),
+        name: "bounds".to_string(),
+        options: vec![
+            mode_option("Specify the mode, defaults to osu!standard"),
+            string_option(
+                "min_stars",
+                "Specify the minimum star rating, unset limits are removed",
+            ),
+            string_option(
+                "max_stars",
+                "Specify the maximum star rating, unset limits are removed",
+            ),
+            string_option("min_length", "Specify the minimum drain length as M:SS"),
+            string_option("max_length", "Specify the maximum drain length as M:SS"),
+        ],
+        required: false,
+    };
+
+    let division_metric_choices = [("Global rank", "rank"), ("PP", "pp")]
+        .iter()
+        .map(|&(name, value)| CommandOptionChoice::String {
+            name: name.to_owned(),
+            value: value.to_owned(),
+        })
+        .collect();
+
+    let division_metric_option = ChoiceCommandOptionData {
+        choices: division_metric_choices,
+        description: "Specify whether players are split by global rank or pp".to_string(),
+        name: "by".to_string(),
+        required: false,
+    };
+
+    let divisions = OptionsCommandOptionData {
+        description: "Show or set the divisions of osuvs rounds. Only admins can use this"
+            .to_string(),
+        name: "divisions".to_string(),
+        options: vec![
+            mode_option("Specify the mode, defaults to osu!standard"),
+            CommandOption::String(division_metric_option),
+            string_option(
+                "thresholds",
+                "Specify comma separated thresholds e.g. `1000, 10000` or `none`",
+            ),
+        ],
+        required: false,
+    };
+
+    let cancel = OptionsCommandOptionData {
+        description: "Remove an upcoming osuvs round without scores. Only admins can use this"
+            .to_string(),
+        name: "cancel".to_string(),
+        options: vec![map_option("Specify the map url of the osuvs round")],
+        required: false,
+    };
+
+    let history = OptionsCommandOptionData {
+        description: "Browse through past osuvs rounds and their winners".to_string(),
+        name: "history".to_string(),
+        options: vec![mode_option("Only show osuvs rounds of this mode")],
+        required: false,
+    };
+
     let info = OptionsCommandOptionData {
         description: "Get information about the current osuvs map".to_string(),
         name: "info".to_string(),
-        options: vec![],
+        options: vec![mode_option("Specify the mode of the osuvs")],
+        required: false,
+    };
+
+    let format_choices = ["csv", "json"]
+        .iter()
+        .map(|&format| CommandOptionChoice::String {
+            name: format.to_uppercase(),
+            value: format.to_owned(),
+        })
+        .collect();
+
+    let format_option = ChoiceCommandOptionData {
+        choices: format_choices,
+        description: "Specify the file format, defaults to CSV".to_string(),
+        name: "format".to_string(),
+        required: false,
+    };
+
+    let export = OptionsCommandOptionData {
+        description: "Export all scores of a round or a season as a file".to_string(),
+        name: "export".to_string(),
+        options: vec![
+            string_option(
+                "round",
+                "Specify the map url or a date (YYYY-MM-DD) of the osuvs",
+            ),
+            string_option("season", "Specify the name of the season"),
+            mode_option("Specify the mode of the osuvs"),
+            CommandOption::String(format_option),
+        ],
+        required: false,
+    };
+
+    let halloffame = OptionsCommandOptionData {
+        description: "Rank all OsuVS players by their wins and podiums".to_string(),
+        name: "halloffame".to_string(),
+        options: vec![mode_option(
+            "Specify the mode of the osuvs, defaults to osu!standard",
+        )],
         required: false,
     };
 
     let leaderboard = OptionsCommandOptionData {
         description: "Get the leaderboard for the current osuvs map".to_string(),
         name: "leaderboard".to_string(),
-        options: vec![],
+        options: vec![
+            mode_option("Specify the mode of the osuvs"),
+            string_option(
+                "mods",
+                "Only show scores with these mods e.g. `HDHR` or `NM`",
+            ),
+            division_option("Only show the leaderboard of a division"),
+        ],
+        required: false,
+    };
+
+    let notifications_option = BaseCommandOptionData {
+        description: "Specify whether you want to receive DMs".to_string(),
+        name: "enabled".to_string(),
+        required: true,
+    };
+
+    let notifications = OptionsCommandOptionData {
+        description: "Get a DM whenever you improve your osuvs score".to_string(),
+        name: "notifications".to_string(),
+        options: vec![CommandOption::Boolean(notifications_option)],
+        required: false,
+    };
+
+    let profile_user_option = BaseCommandOptionData {
+        description: "Specify a user, defaults to yourself".to_string(),
+        name: "user".to_string(),
+        required: false,
+    };
+
+    let profile = OptionsCommandOptionData {
+        description: "Show the OsuVS career of a player".to_string(),
+        name: "profile".to_string(),
+        options: vec![
+            CommandOption::User(profile_user_option),
+            mode_option("Specify the mode of the osuvs, defaults to osu!standard"),
+        ],
+        required: false,
+    };
+
+    let queue = OptionsCommandOptionData {
+        description: "Get the schedule of running and upcoming osuvs rounds".to_string(),
+        name: "queue".to_string(),
+        options: vec![mode_option("Only show osuvs rounds of this mode")],
         required: false,
     };
 
@@ -97,49 +718,360 @@ fn osuvs_options() -> Vec<CommandOption> {
         required: false,
     };
 
-    let request_option = ChoiceCommandOptionData {
+    let request = OptionsCommandOptionData {
+        description: "Request a map for a future osuvs".to_string(),
+        name: "request".to_string(),
+        options: vec![map_option("Specify the map url (difficulty, not mapset)")],
+        required: false,
+    };
+
+    let reschedule = OptionsCommandOptionData {
+        description:
+            "Move an upcoming osuvs round and all following ones. Only admins can use this"
+                .to_string(),
+        name: "reschedule".to_string(),
+        options: vec![
+            map_option("Specify the map url of the osuvs round"),
+            start_date_option(
+                "Specify the new start in UTC (YYYY-MM-DD or YYYY-MM-DD HH:MM)",
+                true,
+            ),
+            duration_option("Specify the new duration in days, defaults to the current one"),
+        ],
+        required: false,
+    };
+
+    let round_option = ChoiceCommandOptionData {
         choices: vec![],
-        description: "Specify the map url (difficulty, not mapset)".to_string(),
-        name: "map".to_string(),
+        description: "Specify the map url or a date (YYYY-MM-DD) of the osuvs".to_string(),
+        name: "round".to_string(),
         required: true,
     };
 
-    let request = OptionsCommandOptionData {
-        description: "Request a map for a future osuvs".to_string(),
-        name: "request".to_string(),
-        options: vec![CommandOption::String(request_option)],
+    let results = OptionsCommandOptionData {
+        description: "Get the full leaderboard of a past osuvs".to_string(),
+        name: "results".to_string(),
+        options: vec![
+            CommandOption::String(round_option),
+            mode_option("Specify the mode of the osuvs"),
+            division_option("Only show the results of a division"),
+        ],
         required: false,
     };
 
-    let start_option = ChoiceCommandOptionData {
+    let season_name_option = ChoiceCommandOptionData {
         choices: vec![],
-        description: "Specify the map url (difficulty, not mapset)".to_string(),
-        name: "map".to_string(),
+        description: "Specify the name of the season".to_string(),
+        name: "name".to_string(),
+        required: true,
+    };
+
+    let season_start_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify the first day of the season (YYYY-MM-DD)".to_string(),
+        name: "start".to_string(),
         required: true,
     };
 
+    let season_end_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify the last day of the season (YYYY-MM-DD)".to_string(),
+        name: "end".to_string(),
+        required: true,
+    };
+
+    let season_points_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify the points per placement e.g. `25,18,15,12,10,8,6,4,2,1`".to_string(),
+        name: "points".to_string(),
+        required: false,
+    };
+
+    let season_participation_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify the points for submitting any score, defaults to 1".to_string(),
+        name: "participation".to_string(),
+        required: false,
+    };
+
+    let season = OptionsCommandOptionData {
+        description: "Create a new osuvs season. Only admins can use this".to_string(),
+        name: "season".to_string(),
+        options: vec![
+            CommandOption::String(season_name_option),
+            CommandOption::String(season_start_option),
+            CommandOption::String(season_end_option),
+            CommandOption::String(season_points_option),
+            CommandOption::Integer(season_participation_option),
+        ],
+        required: false,
+    };
+
+    let standings_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify the name of the season, defaults to the latest one".to_string(),
+        name: "season".to_string(),
+        required: false,
+    };
+
+    let standings = OptionsCommandOptionData {
+        description: "Get the points table of an osuvs season".to_string(),
+        name: "standings".to_string(),
+        options: vec![
+            CommandOption::String(standings_option),
+            mode_option("Specify the mode, defaults to osu!standard"),
+            division_option("Only show the standings of a division"),
+        ],
+        required: false,
+    };
+
+    let required_mods_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify mods that every score must include e.g. HD".to_string(),
+        name: "required_mods".to_string(),
+        required: false,
+    };
+
+    let forbidden_mods_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify mods that are not allowed e.g. DTHR, `all` for nomod only"
+            .to_string(),
+        name: "forbidden_mods".to_string(),
+        required: false,
+    };
+
     let start = OptionsCommandOptionData {
         description: "Start or enqueue a map to the list of osuvs maps".to_string(),
         name: "start".to_string(),
-        options: vec![CommandOption::String(start_option)],
+        options: vec![
+            map_option("Specify the map url (difficulty, not mapset)"),
+            mode_option("Specify the mode, defaults to the mode of the map"),
+            CommandOption::String(required_mods_option),
+            CommandOption::String(forbidden_mods_option),
+            metric_option(
+                "metric",
+                "Specify by what scores are ranked, defaults to score",
+            ),
+            metric_option(
+                "tiebreak",
+                "Specify what decides ties, defaults to the earliest score",
+            ),
+            start_date_option(
+                "Specify the start in UTC (YYYY-MM-DD or YYYY-MM-DD HH:MM), defaults to the end of the queue",
+                false,
+            ),
+            duration_option("Specify the duration in days, defaults to 7"),
+        ],
+        required: false,
+    };
+
+    let submit_option = ChoiceCommandOptionData {
+        choices: vec![],
+        description: "Specify a score id or url, defaults to your recent plays".to_string(),
+        name: "score".to_string(),
+        required: false,
+    };
+
+    let submit = OptionsCommandOptionData {
+        description: "Submit a score for the current osuvs".to_string(),
+        name: "submit".to_string(),
+        options: vec![
+            CommandOption::String(submit_option),
+            mode_option("Specify the mode of the osuvs"),
+        ],
         required: false,
     };
 
     vec![
+        CommandOption::SubCommand(bounds),
+        CommandOption::SubCommand(cancel),
+        CommandOption::SubCommand(divisions),
+        CommandOption::SubCommand(export),
+        CommandOption::SubCommand(halloffame),
+        CommandOption::SubCommand(history),
         CommandOption::SubCommand(info),
         CommandOption::SubCommand(leaderboard),
+        CommandOption::SubCommand(notifications),
+        CommandOption::SubCommand(profile),
+        CommandOption::SubCommand(queue),
         CommandOption::SubCommand(request_list),
         CommandOption::SubCommand(request),
+        CommandOption::SubCommand(reschedule),
+        CommandOption::SubCommand(results),
+        CommandOption::SubCommand(season),
+        CommandOption::SubCommand(standings),
         CommandOption::SubCommand(start),
+        CommandOption::SubCommand(submit),
     ]
 }
 
 async fn osuvs(ctx: Arc<Context>, command: ApplicationCommand, args: OsuVSArgs) -> BotResult<()> {
     match args {
-        OsuVSArgs::Info => info::info(ctx, command).await,
-        OsuVSArgs::Leaderboard => leaderboard::leaderboard(ctx, command).await,
+        OsuVSArgs::Bounds(args) => bounds::bounds(ctx, command, args).await,
+        OsuVSArgs::Cancel(map_id) => cancel::cancel(ctx, command, map_id).await,
+        OsuVSArgs::Divisions(args) => divisions::divisions(ctx, command, args).await,
+        OsuVSArgs::Export(args) => export::export(ctx, command, args).await,
+        OsuVSArgs::HallOfFame(mode) => halloffame::halloffame(ctx, command, mode).await,
+        OsuVSArgs::History(mode) => history::history(ctx, command, mode).await,
+        OsuVSArgs::Info(mode) => info::info(ctx, command, mode).await,
+        OsuVSArgs::Leaderboard {
+            mode,
+            mods,
+            division,
+        } => leaderboard::leaderboard(ctx, command, mode, mods, division).await,
+        OsuVSArgs::Notifications(enabled) => {
+            notifications::notifications(ctx, command, enabled).await
+        }
+        OsuVSArgs::Profile { user, mode } => profile::profile(ctx, command, user, mode).await,
+        OsuVSArgs::Queue(mode) => queue::queue(ctx, command, mode).await,
         OsuVSArgs::RequestList => request_list::request_list(ctx, command).await,
         OsuVSArgs::Request(map_id) => request::request(ctx, command, map_id).await,
-        OsuVSArgs::Start(map_id) => start::start(ctx, command, map_id).await,
+        OsuVSArgs::Reschedule(args) => reschedule::reschedule(ctx, command, args).await,
+        OsuVSArgs::Results {
+            round,
+            mode,
+            division,
+        } => results::results(ctx, command, round, mode, division).await,
+        OsuVSArgs::Season(args) => season::season(ctx, command, args).await,
+        OsuVSArgs::Standings {
+            season,
+            mode,
+            division,
+        } => standings::standings(ctx, command, season, mode, division).await,
+        OsuVSArgs::Start(args) => start::start(ctx, command, args).await,
+        OsuVSArgs::Submit { score_id, mode } => submit::submit(ctx, command, score_id, mode).await,
+    }
+}
+
+/// Get the running osuvs map of the given mode. If no mode is specified,
+/// the only running osuvs map is taken. Otherwise respond with an error.
+async fn curr_osuvs_map(
+    ctx: &Context,
+    command: &ApplicationCommand,
+    mode: Option<GameMode>,
+) -> BotResult<Option<OsuVSMap>> {
+    let mut maps = ctx.database.get_curr_osuvs_maps().await;
+
+    let content = match mode {
+        Some(mode) => match maps.iter().position(|map| map.mode == mode) {
+            Some(idx) => return Ok(Some(maps.swap_remove(idx))),
+            None => format!("There is currently no ongoing {} OsuVS!", mode_name(mode)),
+        },
+        None if maps.len() == 1 => return Ok(maps.pop()),
+        None if maps.is_empty() => "There is currently no ongoing OsuVS!".to_owned(),
+        None => {
+            let modes: Vec<_> = maps.iter().map(|map| mode_name(map.mode)).collect();
+
+            format!(
+                "There are multiple ongoing OsuVS rounds ({}), please specify a mode!",
+                modes.join(", ")
+            )
+        }
+    };
+
+    let builder = MessageBuilder::new().error(content);
+    command.create_message(ctx, builder).await?;
+
+    Ok(None)
+}
+
+/// Only keep the entries of players in the division of the round.
+/// Returns the name of the division or an error message for the user.
+async fn retain_division(
+    ctx: &Context,
+    map_id: u32,
+    division: u8,
+    entries: &mut Vec<OsuVSEntry>,
+) -> BotResult<Result<String, String>> {
+    let divisions = match ctx.database.get_osuvs_map_divisions(map_id).await? {
+        Some(divisions) if (1..=divisions.count()).contains(&division) => divisions,
+        Some(divisions) => {
+            let content = format!("This OsuVS only has divisions 1 to {}!", divisions.count());
+
+            return Ok(Err(content));
+        }
+        None => return Ok(Err("This OsuVS has no divisions!".to_owned())),
+    };
+
+    let members = ctx.database.get_osuvs_division_members(map_id).await?;
+    entries.retain(|entry| members.get(&entry.user_id) == Some(&division));
+
+    Ok(Ok(divisions.name(division)))
+}
+
+/// Append the map to the end of the osuvs queue of the mode and return its start and end date
+async fn enqueue_osuvs_map(
+    ctx: &Context,
+    map_id: u32,
+    mode: GameMode,
+    rules: &OsuVSRules,
+    duration: Duration,
+) -> BotResult<(DateTime<Utc>, DateTime<Utc>)> {
+    let start_date = ctx.database.get_latest_osuvs_date(mode).await?;
+    let end_date = start_date + duration;
+    ctx.database
+        .insert_osuvs_map(map_id, mode, rules, start_date, end_date)
+        .await?;
+
+    Ok((start_date, end_date))
+}
+
+/// Check whether the map can be used for an osuvs of the mode.
+/// If not, the reason is returned.
+async fn validate_map(
+    ctx: &Context,
+    map: &Beatmap,
+    mode: GameMode,
+    calculator: &PpCalculator,
+) -> BotResult<Result<(), String>> {
+    // Only osu!standard maps can be converted to other modes
+    if map.mode != GameMode::STD && map.mode != mode {
+        return Ok(Err(format!(
+            "A {} map can not be played in {}!",
+            mode_name(map.mode),
+            mode_name(mode)
+        )));
+    }
+
+    if !matches!(
+        map.status,
+        RankStatus::Ranked | RankStatus::Approved | RankStatus::Loved
+    ) {
+        return Ok(Err(
+            "Only ranked or loved maps can be used for an OsuVS!".to_owned()
+        ));
+    }
+
+    if ctx.database.osuvs_map_exists(map.map_id).await? {
+        return Ok(Err(
+            "This map has already been used for an OsuVS!".to_owned()
+        ));
+    }
+
+    let bounds = ctx.database.get_osuvs_map_bounds(mode).await?;
+
+    Ok(bounds.check(calculator.stars(GameMods::NoMod), map.seconds_drain))
+}
+
+/// Parse the start of an osuvs round which must lie in the future
+fn parse_start_date(date: &str) -> Result<DateTime<Utc>, String> {
+    match parse_utc_date(date) {
+        Some(date) if date > Utc::now() => Ok(date),
+        Some(_) => Err("The start of the round must be in the future!".to_owned()),
+        None => Err(format!(
+            "Failed to parse `{}` as date, use `YYYY-MM-DD` or `YYYY-MM-DD HH:MM`!",
+            date
+        )),
+    }
+}
+
+fn parse_duration(days: i64) -> Result<Duration, String> {
+    if (1..=MAX_DURATION_DAYS).contains(&days) {
+        Ok(Duration::days(days))
+    } else {
+        Err(format!(
+            "The duration must be between 1 and {} days!",
+            MAX_DURATION_DAYS
+        ))
     }
 }
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+pub async fn notifications(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    enabled: bool,
+) -> BotResult<()> {
+    let user_id = command.user_id()?;
+
+    let content = if enabled {
+        if ctx.database.get_manual_link(user_id).await?.is_none() {
+            let builder = MessageBuilder::new()
+                .error("You need to link your osu! account to receive OsuVS notifications!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+
+        ctx.database.insert_osuvs_notification(user_id).await?;
+
+        "You will now receive a DM whenever you improve your OsuVS score"
+    } else {
+        ctx.database.remove_osuvs_notification(user_id).await?;
+
+        "You will no longer receive DMs about your OsuVS scores"
+    };
+
+    let builder = MessageBuilder::new().embed(content).ephemeral();
+
+    command.create_message(&ctx, builder).await
+}
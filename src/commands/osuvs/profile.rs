@@ -0,0 +1,109 @@
+use std::{fmt::Write, sync::Arc};
+
+use rosu_v2::prelude::GameMode;
+use twilight_model::{application::interaction::ApplicationCommand, id::UserId};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{
+        numbers::round, osu::mode_name, osuvs::osuvs_career, ApplicationCommandExt, Author,
+        EmbedBuilder, Footer, MessageBuilder, OSU_BASE,
+    },
+};
+
+pub async fn profile(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    user: Option<UserId>,
+    mode: Option<GameMode>,
+) -> BotResult<()> {
+    let discord_id = match user {
+        Some(user) => user,
+        None => command.user_id()?,
+    };
+
+    let osu_id = match ctx.database.get_manual_link(discord_id).await? {
+        Some(osu_id) => osu_id,
+        None => {
+            let content = if user.is_some() {
+                "That user has not linked their osu! account!"
+            } else {
+                "You have not linked your osu! account!"
+            };
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let mode = mode.unwrap_or(GameMode::STD);
+    info!("Displaying osuvs profile of user {}...", osu_id);
+
+    let rounds = ctx.database.get_osuvs_career(mode, osu_id).await?;
+    let career = osuvs_career(&rounds);
+
+    if career.rounds == 0 {
+        let content = format!(
+            "<@{}> has not played any {} OsuVS round yet!",
+            discord_id,
+            mode_name(mode)
+        );
+        let builder = MessageBuilder::new().error(content);
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let mut description = String::new();
+
+    let _ = writeln!(
+        description,
+        "**Rounds played:** {} of {}\n\
+        **Wins:** {}\n\
+        **Podiums:** {}",
+        career.rounds, career.total_rounds, career.wins, career.podiums,
+    );
+
+    if let Some(average) = career.average_placement() {
+        let _ = writeln!(description, "**Average placement:** #{}", round(average));
+    }
+
+    if let Some((placement, map_id)) = career.best {
+        let _ = writeln!(
+            description,
+            "**Best finish:** #{} on [this map]({}b/{})",
+            placement + 1,
+            OSU_BASE,
+            map_id
+        );
+    }
+
+    if let Some((mods, count)) = career.favourite_mods {
+        let _ = writeln!(
+            description,
+            "**Favourite mods:** {} ({} round{})",
+            mods,
+            count,
+            if count == 1 { "" } else { "s" }
+        );
+    }
+
+    let _ = write!(
+        description,
+        "**Participation streak:** {} (longest {})",
+        career.current_streak, career.longest_streak
+    );
+
+    let username = career.username.unwrap_or_else(|| osu_id.to_string());
+
+    let author = Author::new(format!("{} OsuVS profile of {}", mode_name(mode), username))
+        .url(format!("{}users/{}", OSU_BASE, osu_id));
+
+    let embed = EmbedBuilder::new()
+        .author(author)
+        .thumbnail(format!("https://a.ppy.sh/{}", osu_id))
+        .description(description)
+        .footer(Footer::new("Only finished rounds are counted"));
+
+    command.create_message(&ctx, embed).await
+}
@@ -0,0 +1,110 @@
+use std::{fmt::Write, sync::Arc};
+
+use chrono::Utc;
+use futures::future::try_join_all;
+use rosu_v2::prelude::GameMode;
+use twilight_model::{application::interaction::ApplicationCommand, channel::embed::Embed};
+
+use crate::{
+    context::Context,
+    database::OsuVSMap,
+    error::BotResult,
+    utils::{
+        osu::{map_to_string, mode_name},
+        pagination::{page_buttons, ComponentStream, Pages},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, MessageComponentExt,
+        OSU_BASE,
+    },
+};
+
+const ROUNDS_PER_PAGE: usize = 10;
+
+pub async fn queue(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    mode: Option<GameMode>,
+) -> BotResult<()> {
+    let mut osuvs_maps = ctx.database.get_upcoming_osuvs_maps().await?;
+
+    if let Some(mode) = mode {
+        osuvs_maps.retain(|osuvs_map| osuvs_map.mode == mode);
+    }
+
+    if osuvs_maps.is_empty() {
+        let builder = MessageBuilder::new().error("There are no upcoming OsuVS rounds!");
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Displaying osuvs queue...");
+    let mut pages = Pages::new(ROUNDS_PER_PAGE, osuvs_maps.len());
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, command.user_id()?);
+
+    // Requesting the maps might take a while
+    command.start_thinking(&ctx).await?;
+
+    let mut embed = queue_embed(&ctx, &osuvs_maps, &pages).await?;
+    let components = [page_buttons(&pages)];
+    let builder = MessageBuilder::new()
+        .embed(embed.clone())
+        .components(&components);
+    command.update_message(&ctx, builder).await?;
+
+    while let Some(component) = stream.next().await {
+        if !pages.update(&component.data.custom_id) {
+            continue;
+        }
+
+        embed = queue_embed(&ctx, &osuvs_maps, &pages).await?;
+        let components = [page_buttons(&pages)];
+        let builder = MessageBuilder::new()
+            .embed(embed.clone())
+            .components(&components);
+        component.update_message(&ctx, builder).await?;
+    }
+
+    // Timed out, remove the buttons
+    command.update_message(&ctx, embed).await
+}
+
+async fn queue_embed(ctx: &Context, osuvs_maps: &[OsuVSMap], pages: &Pages) -> BotResult<Embed> {
+    let entries = &osuvs_maps[pages.index..osuvs_maps.len().min(pages.index + pages.per_page)];
+
+    let map_futs = entries
+        .iter()
+        .map(|osuvs_map| ctx.osu.beatmap().map_id(osuvs_map.map_id));
+    let maps = try_join_all(map_futs).await?;
+
+    let now = Utc::now();
+    let mut description = String::new();
+
+    for ((osuvs_map, map), i) in entries.iter().zip(maps).zip(pages.index + 1..) {
+        let _ = writeln!(
+            description,
+            "**{}.** [{}]({}b/{}) `{}`\n\
+            - <t:{}:f> until <t:{}:f>{}",
+            i,
+            map_to_string(&map),
+            OSU_BASE,
+            osuvs_map.map_id,
+            mode_name(osuvs_map.mode),
+            osuvs_map.start_date.timestamp(),
+            osuvs_map.end_date.timestamp(),
+            if osuvs_map.start_date <= now {
+                " • **running**"
+            } else {
+                ""
+            },
+        );
+    }
+
+    let embed = EmbedBuilder::new()
+        .author(Author::new("Upcoming OsuVS rounds"))
+        .description(description)
+        .footer(Footer::new(pages.footer_text()))
+        .build();
+
+    Ok(embed)
+}
@@ -1,13 +1,123 @@
 use std::sync::Arc;
 
+use rosu_v2::prelude::{GameMods, OsuError};
 use twilight_model::application::interaction::ApplicationCommand;
 
-use crate::{context::Context, error::BotResult};
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{
+        datetime::sec_to_minsec,
+        numbers::round,
+        osu::{map_to_string, PpCalculator},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, OSU_API_ISSUE,
+        OSU_BASE,
+    },
+};
+
+use super::validate_map;
 
 pub async fn request(
     ctx: Arc<Context>,
     command: ApplicationCommand,
     map_id: Option<u32>,
 ) -> BotResult<()> {
-    todo!()
+    let map_id = match map_id {
+        Some(id) => id,
+        None => {
+            let builder = MessageBuilder::new()
+                .error("Please specify a valid map url of a difficulty, not of a mapset!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    if ctx.database.osuvs_map_exists(map_id).await? {
+        let builder = MessageBuilder::new().error("This map has already been used for an OsuVS!");
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    if ctx.database.osuvs_request_exists(map_id).await? {
+        let builder = MessageBuilder::new().error("This map has already been requested!");
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    // Downloading the map and calculating its difficulty might take a while
+    command.start_thinking(&ctx).await?;
+
+    let map = match ctx.osu.beatmap().map_id(map_id).await {
+        Ok(map) => map,
+        Err(OsuError::NotFound) => {
+            let content = format!("Could not find a map with id `{}`!", map_id);
+            let builder = MessageBuilder::new().error(content);
+
+            return command.update_message(&ctx, builder).await;
+        }
+        Err(why) => {
+            let builder = MessageBuilder::new().error(OSU_API_ISSUE);
+            let _ = command.update_message(&ctx, builder).await;
+
+            return Err(why.into());
+        }
+    };
+
+    let calculator = PpCalculator::new(
+        &ctx,
+        map_id,
+        map.checksum.as_deref(),
+        map.mode,
+        &[GameMods::NoMod],
+    )
+    .await?;
+
+    if let Err(content) = validate_map(&ctx, &map, map.mode, &calculator).await? {
+        let builder = MessageBuilder::new().error(content);
+
+        return command.update_message(&ctx, builder).await;
+    }
+
+    info!("Adding map {} to osuvs requests...", map_id);
+
+    // Another request for the same map might have been inserted in the meantime
+    if !ctx
+        .database
+        .insert_osuvs_request(&map, command.user_id()?)
+        .await?
+    {
+        let builder = MessageBuilder::new().error("This map has already been requested!");
+
+        return command.update_message(&ctx, builder).await;
+    }
+
+    let title = map_to_string(&map);
+    let url = format!("{}b/{}", OSU_BASE, map_id);
+    let author = Author::new("Your OsuVS request has been submitted!");
+    let description = format!(
+        "Stars: `{}★` Length: `{}` (`{}`) Combo: `{}x`\n\
+        CS: `{}` HP: `{}` OD: `{}` AR: `{}`",
+        round(map.stars),
+        sec_to_minsec(map.seconds_total),
+        sec_to_minsec(map.seconds_drain),
+        map.max_combo.unwrap_or(0),
+        round(map.cs),
+        round(map.hp),
+        round(map.od),
+        round(map.ar)
+    );
+    let image = format!(
+        "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
+        map.mapset_id
+    );
+    let footer = Footer::new(format!("Requested by {}", command.username()?));
+    let builder = EmbedBuilder::new()
+        .title(title)
+        .url(url)
+        .author(author)
+        .description(description)
+        .image(image)
+        .footer(footer);
+
+    command.update_message(&ctx, builder).await
 }
@@ -1,9 +1,259 @@
-use std::sync::Arc;
+use std::{fmt::Write, sync::Arc};
 
-use twilight_model::application::interaction::ApplicationCommand;
+use chrono::Duration;
+use twilight_model::application::{
+    component::{button::ButtonStyle, ActionRow, Button, Component},
+    interaction::ApplicationCommand,
+};
 
-use crate::{context::Context, error::BotResult};
+use crate::{
+    commands::{check_permission, permission_denied, PERMISSION_OSUVS_ADMIN},
+    context::Context,
+    database::{OsuVSRequest, OsuVSRules},
+    error::BotResult,
+    utils::{
+        datetime::sec_to_minsec,
+        numbers::round,
+        osu::map_to_string,
+        pagination::{page_buttons, ComponentStream, Pages},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, MessageComponentExt,
+        OSUVS_DATE_FORMAT, OSU_BASE,
+    },
+};
+
+use super::{enqueue_osuvs_map, DEFAULT_DURATION_DAYS};
+
+const REQUESTS_PER_PAGE: usize = 5;
+const ACCEPT_PREFIX: &str = "accept_";
+const REJECT_PREFIX: &str = "reject_";
 
 pub async fn request_list(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
-    todo!()
+    if !check_permission(&ctx, &command, PERMISSION_OSUVS_ADMIN).await? {
+        return permission_denied(&ctx, &command).await;
+    }
+
+    let mut requests = ctx.database.get_osuvs_requests().await?;
+
+    if requests.is_empty() {
+        let builder = MessageBuilder::new().embed("There are currently no pending OsuVS requests!");
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let mut pages = Pages::new(REQUESTS_PER_PAGE, requests.len());
+    let mut status = None;
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, command.user_id()?);
+
+    let embed = request_embed(&requests, &pages, status.as_deref());
+    let components = request_components(&requests, &pages);
+    let builder = MessageBuilder::new().embed(embed).components(&components);
+    command.create_message(&ctx, builder).await?;
+
+    while let Some(component) = stream.next().await {
+        let custom_id = component.data.custom_id.as_str();
+
+        if !pages.update(custom_id) {
+            let action = parse_action(custom_id, ACCEPT_PREFIX)
+                .map(|map_id| (map_id, true))
+                .or_else(|| parse_action(custom_id, REJECT_PREFIX).map(|map_id| (map_id, false)));
+
+            if let Some((map_id, accept)) = action {
+                if let Some(idx) = requests.iter().position(|r| r.map.map_id == map_id) {
+                    let result = if accept {
+                        accept_request(&ctx, &requests[idx]).await
+                    } else {
+                        reject_request(&ctx, &requests[idx]).await
+                    };
+
+                    // Failed requests stay in the list and the button press is still answered
+                    status = match result {
+                        Ok(status) => {
+                            requests.remove(idx);
+
+                            Some(status)
+                        }
+                        Err(why) => {
+                            unwind_error!(error, why, "Error while handling osuvs request: {}");
+
+                            Some(format!(
+                                ":warning: Failed to handle `{}`, please try again",
+                                map_to_string(&requests[idx].map)
+                            ))
+                        }
+                    };
+                }
+            }
+
+            pages.set_total(requests.len());
+        }
+
+        if requests.is_empty() {
+            let description = format!(
+                "{}\n\nAll OsuVS requests have been handled!",
+                status.as_deref().unwrap_or_default()
+            );
+            let builder = MessageBuilder::new().embed(description);
+
+            return component.update_message(&ctx, builder).await;
+        }
+
+        let embed = request_embed(&requests, &pages, status.as_deref());
+        let components = request_components(&requests, &pages);
+        let builder = MessageBuilder::new().embed(embed).components(&components);
+        component.update_message(&ctx, builder).await?;
+    }
+
+    // Timed out, remove the buttons
+    let embed = request_embed(&requests, &pages, status.as_deref());
+    command.update_message(&ctx, embed).await
+}
+
+fn parse_action(custom_id: &str, prefix: &str) -> Option<u32> {
+    custom_id
+        .strip_prefix(prefix)
+        .and_then(|map_id| map_id.parse().ok())
+}
+
+async fn accept_request(ctx: &Context, request: &OsuVSRequest) -> BotResult<String> {
+    let map = &request.map;
+    let title = map_to_string(map);
+
+    if ctx.database.osuvs_map_exists(map.map_id).await? {
+        ctx.database.remove_osuvs_request(map.map_id).await?;
+
+        return Ok(format!(
+            ":warning: `{}` was already used for an OsuVS, the request has been removed",
+            title
+        ));
+    }
+
+    info!("Accepting osuvs request for map {}...", map.map_id);
+    let (start_date, end_date) = enqueue_osuvs_map(
+        ctx,
+        map.map_id,
+        map.mode,
+        &OsuVSRules::default(),
+        Duration::days(DEFAULT_DURATION_DAYS),
+    )
+    .await?;
+
+    // Only removed once enqueued so a failure doesn't lose the request
+    ctx.database.remove_osuvs_request(map.map_id).await?;
+
+    let description = format!(
+        "Your OsuVS request for [{}]({}b/{}) has been accepted!\n\
+        It will be played from {} until {}.",
+        title,
+        OSU_BASE,
+        map.map_id,
+        start_date.format(OSUVS_DATE_FORMAT),
+        end_date.format(OSUVS_DATE_FORMAT)
+    );
+    let embed = EmbedBuilder::new().description(description).build();
+
+    if let Err(why) = ctx.send_dm(request.requester, embed).await {
+        unwind_error!(
+            warn,
+            why,
+            "Could not notify requester of accepted request: {}"
+        );
+    }
+
+    Ok(format!(
+        ":white_check_mark: Accepted `{}`, it starts on {}",
+        title,
+        start_date.format(OSUVS_DATE_FORMAT)
+    ))
+}
+
+async fn reject_request(ctx: &Context, request: &OsuVSRequest) -> BotResult<String> {
+    let map = &request.map;
+    let title = map_to_string(map);
+
+    info!("Rejecting osuvs request for map {}...", map.map_id);
+    ctx.database.remove_osuvs_request(map.map_id).await?;
+
+    let description = format!(
+        "Your OsuVS request for [{}]({}b/{}) has been rejected.",
+        title, OSU_BASE, map.map_id,
+    );
+    let embed = EmbedBuilder::new().description(description).build();
+
+    if let Err(why) = ctx.send_dm(request.requester, embed).await {
+        unwind_error!(
+            warn,
+            why,
+            "Could not notify requester of rejected request: {}"
+        );
+    }
+
+    Ok(format!(":x: Rejected `{}`", title))
+}
+
+fn request_embed(requests: &[OsuVSRequest], pages: &Pages, status: Option<&str>) -> EmbedBuilder {
+    let mut description = String::new();
+
+    if let Some(status) = status {
+        let _ = writeln!(description, "{}\n", status);
+    }
+
+    let entries = requests.iter().skip(pages.index).take(pages.per_page);
+
+    for (request, i) in entries.zip(pages.index + 1..) {
+        let map = &request.map;
+
+        let _ = writeln!(
+            description,
+            "**{}.** [{}]({}b/{})\n\
+            - `{}★` - `{}` - requested by <@{}> <t:{}:R>",
+            i,
+            map_to_string(map),
+            OSU_BASE,
+            map.map_id,
+            round(map.stars),
+            sec_to_minsec(map.seconds_total),
+            request.requester,
+            request.requested_at.timestamp()
+        );
+    }
+
+    EmbedBuilder::new()
+        .author(Author::new("Pending OsuVS requests"))
+        .description(description)
+        .footer(Footer::new(pages.footer_text()))
+}
+
+fn request_components(requests: &[OsuVSRequest], pages: &Pages) -> Vec<Component> {
+    let entries = requests.iter().skip(pages.index).take(pages.per_page);
+    let mut accept = Vec::with_capacity(pages.per_page);
+    let mut reject = Vec::with_capacity(pages.per_page);
+
+    for (request, i) in entries.zip(pages.index + 1..) {
+        let map_id = request.map.map_id;
+
+        accept.push(Component::Button(Button {
+            custom_id: Some(format!("{}{}", ACCEPT_PREFIX, map_id)),
+            disabled: false,
+            emoji: None,
+            label: Some(format!("Accept {}", i)),
+            style: ButtonStyle::Success,
+            url: None,
+        }));
+
+        reject.push(Component::Button(Button {
+            custom_id: Some(format!("{}{}", REJECT_PREFIX, map_id)),
+            disabled: false,
+            emoji: None,
+            label: Some(format!("Reject {}", i)),
+            style: ButtonStyle::Danger,
+            url: None,
+        }));
+    }
+
+    vec![
+        page_buttons(pages),
+        Component::ActionRow(ActionRow { components: accept }),
+        Component::ActionRow(ActionRow { components: reject }),
+    ]
 }
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::{check_permission, permission_denied, PERMISSION_OSUVS_ADMIN},
+    context::Context,
+    error::BotResult,
+    utils::{osu::mode_name, ApplicationCommandExt, MessageBuilder, OSUVS_DATE_FORMAT},
+};
+
+use super::{parse_duration, parse_start_date};
+
+pub struct RescheduleArgs {
+    pub map_id: Option<u32>,
+    pub start: String,
+    pub duration: Option<i64>,
+}
+
+pub async fn reschedule(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: RescheduleArgs,
+) -> BotResult<()> {
+    if !check_permission(&ctx, &command, PERMISSION_OSUVS_ADMIN).await? {
+        return permission_denied(&ctx, &command).await;
+    }
+
+    let map_id = match args.map_id {
+        Some(map_id) => map_id,
+        None => {
+            let builder = MessageBuilder::new()
+                .error("Please specify a valid map url of a difficulty, not of a mapset!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let parsed = parse_start_date(&args.start).and_then(|start_date| {
+        args.duration
+            .map(parse_duration)
+            .transpose()
+            .map(|duration| (start_date, duration))
+    });
+
+    let (start_date, duration) = match parsed {
+        Ok(parsed) => parsed,
+        Err(content) => {
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let now = Utc::now();
+    let mut rounds = ctx.database.get_upcoming_osuvs_maps().await?;
+
+    let idx = match rounds.iter().position(|round| round.map_id == map_id) {
+        Some(idx) if rounds[idx].start_date > now => idx,
+        Some(_) => {
+            let builder = MessageBuilder::new()
+                .error("This OsuVS round already started, only upcoming rounds can be moved!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+        None => {
+            let builder = MessageBuilder::new().error("This map is not part of the OsuVS queue!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let osuvs_map = rounds[idx];
+    rounds.retain(|round| round.mode == osuvs_map.mode);
+
+    let duration = duration.unwrap_or(osuvs_map.end_date - osuvs_map.start_date);
+    let end_date = start_date + duration;
+
+    // Rounds scheduled before this one stay where they are
+    let previous = rounds
+        .iter()
+        .filter(|round| round.start_date < osuvs_map.start_date)
+        .find(|round| round.end_date > start_date);
+
+    if let Some(previous) = previous {
+        let content = format!(
+            "The round would overlap with the previous {} OsuVS round ending {}!",
+            mode_name(previous.mode),
+            previous.end_date.format(OSUVS_DATE_FORMAT)
+        );
+        let builder = MessageBuilder::new().error(content);
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    // All following rounds are shifted by the same amount as the end of this one
+    let shift = end_date - osuvs_map.end_date;
+
+    let mut dates = vec![(map_id, start_date, end_date)];
+
+    dates.extend(
+        rounds
+            .iter()
+            .filter(|round| round.start_date >= osuvs_map.end_date)
+            .map(|round| {
+                (
+                    round.map_id,
+                    round.start_date + shift,
+                    round.end_date + shift,
+                )
+            }),
+    );
+
+    info!("Rescheduling osuvs map {}...", map_id);
+    ctx.database.update_osuvs_map_dates(&dates).await?;
+
+    let content = format!(
+        "The {} OsuVS round now runs from {} until {}.\n\
+        {} following round{} moved along with it.",
+        mode_name(osuvs_map.mode),
+        start_date.format(OSUVS_DATE_FORMAT),
+        end_date.format(OSUVS_DATE_FORMAT),
+        dates.len() - 1,
+        if dates.len() == 2 { " was" } else { "s were" }
+    );
+    let builder = MessageBuilder::new().embed(content);
+
+    command.create_message(&ctx, builder).await
+}
@@ -0,0 +1,196 @@
+use std::{fmt::Write, sync::Arc};
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use rosu_v2::prelude::{Beatmap, GameMode};
+use twilight_model::{application::interaction::ApplicationCommand, channel::embed::Embed};
+
+use crate::{
+    context::Context,
+    database::OsuVSMap,
+    error::BotResult,
+    utils::{
+        osu::{get_osu_map_id, map_to_string, mode_name},
+        osuvs::{leaderboard_page, osuvs_leaderboard, OsuVSEntry},
+        pagination::{page_buttons, ComponentStream, Pages},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, MessageComponentExt,
+        OSU_BASE,
+    },
+};
+
+use super::retain_division;
+
+const ENTRIES_PER_PAGE: usize = 10;
+
+pub async fn results(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    round: String,
+    mode: Option<GameMode>,
+    division: Option<u8>,
+) -> BotResult<()> {
+    let osuvs_map = match find_round(&ctx, &round, mode).await {
+        Ok(osuvs_map) => osuvs_map,
+        Err(content) => {
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    info!("Displaying results of osuvs map {}...", osuvs_map.map_id);
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, command.user_id()?);
+
+    // Calculating the pp of all scores might take a while
+    command.start_thinking(&ctx).await?;
+
+    let map = ctx.osu.beatmap().map_id(osuvs_map.map_id).await?;
+    let mut entries = osuvs_leaderboard(&ctx, &osuvs_map, usize::MAX).await?;
+
+    let division = match division {
+        Some(division) => {
+            match retain_division(&ctx, osuvs_map.map_id, division, &mut entries).await? {
+                Ok(name) => Some(name),
+                Err(content) => {
+                    let builder = MessageBuilder::new().error(content);
+
+                    return command.update_message(&ctx, builder).await;
+                }
+            }
+        }
+        None => None,
+    };
+    let division = division.as_deref();
+
+    if entries.is_empty() {
+        let embed = EmbedBuilder::new()
+            .title(map_to_string(&map))
+            .url(format!("{}b/{}", OSU_BASE, osuvs_map.map_id))
+            .description("No scores were submitted for this OsuVS!");
+
+        return command.update_message(&ctx, embed).await;
+    }
+
+    let mut pages = Pages::new(ENTRIES_PER_PAGE, entries.len());
+    let embed = results_embed(&osuvs_map, &map, division, &entries, &pages);
+
+    if pages.last_page() == 1 {
+        return command.update_message(&ctx, embed).await;
+    }
+
+    let components = [page_buttons(&pages)];
+    let builder = MessageBuilder::new().embed(embed).components(&components);
+    command.update_message(&ctx, builder).await?;
+
+    while let Some(component) = stream.next().await {
+        if !pages.update(&component.data.custom_id) {
+            continue;
+        }
+
+        let embed = results_embed(&osuvs_map, &map, division, &entries, &pages);
+        let components = [page_buttons(&pages)];
+        let builder = MessageBuilder::new().embed(embed).components(&components);
+        component.update_message(&ctx, builder).await?;
+    }
+
+    // Timed out, remove the buttons
+    let embed = results_embed(&osuvs_map, &map, division, &entries, &pages);
+    command.update_message(&ctx, embed).await
+}
+
+/// Find the osuvs round either by its map or by a date during which it was running
+pub(super) async fn find_round(
+    ctx: &Context,
+    round: &str,
+    mode: Option<GameMode>,
+) -> Result<OsuVSMap, String> {
+    let osuvs_map = if let Ok(date) = NaiveDate::parse_from_str(round, "%F") {
+        let date = Utc.from_utc_date(&date).and_hms(12, 0, 0);
+
+        let mut osuvs_maps = match ctx.database.get_osuvs_maps_at(date).await {
+            Ok(osuvs_maps) => osuvs_maps,
+            Err(why) => {
+                unwind_error!(error, why, "Error while getting osuvs maps by date: {}");
+
+                return Err("Failed to retrieve the OsuVS rounds of that date".to_owned());
+            }
+        };
+
+        if let Some(mode) = mode {
+            osuvs_maps.retain(|osuvs_map| osuvs_map.mode == mode);
+        }
+
+        match osuvs_maps.len() {
+            0 => return Err(format!("There was no OsuVS running on {}!", round)),
+            1 => osuvs_maps.pop().unwrap(),
+            _ => {
+                return Err(format!(
+                    "There were multiple OsuVS rounds running on {}, please specify a mode!",
+                    round
+                ))
+            }
+        }
+    } else if let Some(map_id) = get_osu_map_id(round) {
+        match ctx.database.get_osuvs_map(map_id).await {
+            Ok(Some(osuvs_map)) => osuvs_map,
+            Ok(None) => return Err("This map has not been used for an OsuVS!".to_owned()),
+            Err(why) => {
+                unwind_error!(error, why, "Error while getting osuvs map: {}");
+
+                return Err("Failed to retrieve the OsuVS round of that map".to_owned());
+            }
+        }
+    } else {
+        return Err(
+            "Please specify either a map url or a date in the format `YYYY-MM-DD`!".to_owned(),
+        );
+    };
+
+    if osuvs_map.start_date > Utc::now() {
+        return Err("This OsuVS has not started yet!".to_owned());
+    }
+
+    Ok(osuvs_map)
+}
+
+fn results_embed(
+    osuvs_map: &OsuVSMap,
+    map: &Beatmap,
+    division: Option<&str>,
+    entries: &[OsuVSEntry],
+    pages: &Pages,
+) -> Embed {
+    let max_combo = map.max_combo.unwrap_or(0);
+    let description = leaderboard_page(entries, pages, max_combo, None);
+
+    let mut author = if osuvs_map.end_date > Utc::now() {
+        format!("Current results of the {} OsuVS", mode_name(osuvs_map.mode))
+    } else {
+        format!("Final results of the {} OsuVS", mode_name(osuvs_map.mode))
+    };
+
+    if let Some(division) = division {
+        let _ = write!(author, " • {}", division);
+    }
+
+    let footer = format!(
+        "{} • {} until {}",
+        pages.footer_text(),
+        osuvs_map.start_date.format("%F"),
+        osuvs_map.end_date.format("%F")
+    );
+
+    EmbedBuilder::new()
+        .title(map_to_string(map))
+        .url(format!("{}b/{}", OSU_BASE, osuvs_map.map_id))
+        .thumbnail(format!("https://a.ppy.sh/{}", entries[0].user_id))
+        .author(Author::new(author))
+        .description(description)
+        .image(format!(
+            "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
+            map.mapset_id
+        ))
+        .footer(Footer::new(footer))
+        .build()
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    commands::{check_permission, permission_denied, PERMISSION_OSUVS_ADMIN},
+    context::Context,
+    database::{OsuVSSeason, DEFAULT_PARTICIPATION_POINTS, DEFAULT_SEASON_POINTS},
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+pub struct SeasonArgs {
+    pub name: String,
+    pub start: String,
+    pub end: String,
+    pub points: Option<String>,
+    pub participation: Option<i64>,
+}
+
+pub async fn season(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: SeasonArgs,
+) -> BotResult<()> {
+    if !check_permission(&ctx, &command, PERMISSION_OSUVS_ADMIN).await? {
+        return permission_denied(&ctx, &command).await;
+    }
+
+    let season = match parse_season(args) {
+        Ok(season) => season,
+        Err(content) => {
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    if ctx
+        .database
+        .osuvs_season_overlaps(season.start_date, season.end_date)
+        .await?
+    {
+        let builder = MessageBuilder::new().error("This season overlaps with another season!");
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    info!("Adding osuvs season {}...", season.name);
+
+    if !ctx.database.insert_osuvs_season(&season).await? {
+        let builder = MessageBuilder::new().error("A season with this name already exists!");
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let points: Vec<_> = season.points.iter().map(u32::to_string).collect();
+
+    let content = format!(
+        "Season `{}` runs from {} until {}\n\
+        Points: `{}` + `{}` for participating",
+        season.name,
+        season.start_date.format("%F"),
+        (season.end_date - Duration::days(1)).format("%F"),
+        points.join(", "),
+        season.participation_points
+    );
+    let builder = MessageBuilder::new().embed(content);
+
+    command.create_message(&ctx, builder).await
+}
+
+fn parse_season(args: SeasonArgs) -> Result<OsuVSSeason, String> {
+    let parse_date = |date: &str| {
+        NaiveDate::parse_from_str(date, "%F")
+            .map(|date| Utc.from_utc_date(&date).and_hms(0, 0, 0))
+            .map_err(|_| format!("Failed to parse `{}` as date, use `YYYY-MM-DD`!", date))
+    };
+
+    let start_date = parse_date(&args.start)?;
+
+    // The end date itself still belongs to the season
+    let end_date = parse_date(&args.end)? + Duration::days(1);
+
+    if end_date <= start_date {
+        return Err("The season must end after it starts!".to_owned());
+    }
+
+    let points = match args.points {
+        Some(points) => points
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|points| !points.is_empty())
+            .map(|points| {
+                points
+                    .parse()
+                    .map_err(|_| format!("Failed to parse `{}` as points!", points))
+            })
+            .collect::<Result<Vec<u32>, _>>()?,
+        None => DEFAULT_SEASON_POINTS.to_vec(),
+    };
+
+    let participation_points = match args.participation {
+        Some(points) if points < 0 => {
+            return Err("Participation points can not be negative!".to_owned())
+        }
+        Some(points) => points as u32,
+        None => DEFAULT_PARTICIPATION_POINTS,
+    };
+
+    Ok(OsuVSSeason {
+        name: args.name,
+        start_date,
+        end_date,
+        points,
+        participation_points,
+    })
+}
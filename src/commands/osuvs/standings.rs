@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use rosu_v2::prelude::GameMode;
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{
+        osuvs::{season_standings, standings_embed},
+        pagination::{page_buttons, ComponentStream, Pages},
+        ApplicationCommandExt, MessageBuilder, MessageComponentExt,
+    },
+};
+
+const ENTRIES_PER_PAGE: usize = 10;
+
+pub async fn standings(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    season: Option<String>,
+    mode: Option<GameMode>,
+    division: Option<u8>,
+) -> BotResult<()> {
+    let season = match season {
+        Some(name) => ctx.database.get_osuvs_season(&name).await?,
+        None => ctx.database.get_latest_osuvs_season().await?,
+    };
+
+    let season = match season {
+        Some(season) => season,
+        None => {
+            let builder = MessageBuilder::new().error("Could not find that OsuVS season!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    info!("Displaying osuvs standings of season {}...", season.name);
+    let mode = mode.unwrap_or(GameMode::STD);
+    let standings = season_standings(&ctx, &season, mode, Utc::now(), division).await?;
+    let mut pages = Pages::new(ENTRIES_PER_PAGE, standings.len());
+    let embed = standings_embed(&season, mode, division, &standings, &pages);
+
+    if pages.last_page() == 1 {
+        return command.create_message(&ctx, embed).await;
+    }
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, command.user_id()?);
+
+    let components = [page_buttons(&pages)];
+    let builder = MessageBuilder::new().embed(embed).components(&components);
+    command.create_message(&ctx, builder).await?;
+
+    while let Some(component) = stream.next().await {
+        if !pages.update(&component.data.custom_id) {
+            continue;
+        }
+
+        let embed = standings_embed(&season, mode, division, &standings, &pages);
+        let components = [page_buttons(&pages)];
+        let builder = MessageBuilder::new().embed(embed).components(&components);
+        component.update_message(&ctx, builder).await?;
+    }
+
+    // Timed out, remove the buttons
+    let embed = standings_embed(&season, mode, division, &standings, &pages);
+    command.update_message(&ctx, embed).await
+}
@@ -1,46 +1,379 @@
-use std::sync::Arc;
+use std::{fmt::Write, str::FromStr, sync::Arc};
 
-use chrono::Duration;
-use twilight_model::{application::interaction::ApplicationCommand, id::UserId};
+use chrono::{DateTime, Duration, Utc};
+use rosu_v2::prelude::{Beatmap, GameMode, GameMods, OsuError};
+use twilight_model::{
+    application::{
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+        interaction::ApplicationCommand,
+    },
+    channel::embed::Embed,
+};
 
 use crate::{
+    commands::{check_permission, permission_denied, PERMISSION_OSUVS_ADMIN},
     context::Context,
+    database::{OsuVSMetric, OsuVSRules},
     error::BotResult,
-    utils::{ApplicationCommandExt, MessageBuilder},
+    utils::{
+        datetime::sec_to_minsec,
+        numbers::round,
+        osu::{map_to_string, mode_name, PpCalculator},
+        pagination::ComponentStream,
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, MessageComponentExt,
+        OSUVS_DATE_FORMAT, OSU_API_ISSUE, OSU_BASE,
+    },
+};
+
+use super::{
+    enqueue_osuvs_map, parse_duration, parse_start_date, validate_map, DEFAULT_DURATION_DAYS,
 };
 
+const BUTTON_CONFIRM: &str = "confirm";
+const BUTTON_CANCEL: &str = "cancel";
+const PREVIEW_ACCURACIES: [f64; 4] = [100.0, 99.0, 98.0, 97.0];
+
+#[derive(Default)]
+pub struct StartArgs {
+    pub map_id: Option<u32>,
+    pub mode: Option<GameMode>,
+    pub required_mods: Option<String>,
+    pub forbidden_mods: Option<String>,
+    pub metric: Option<OsuVSMetric>,
+    pub tiebreak: Option<OsuVSMetric>,
+    pub start: Option<String>,
+    pub duration: Option<i64>,
+}
+
 pub async fn start(
     ctx: Arc<Context>,
     command: ApplicationCommand,
-    map_id: Option<u32>,
+    args: StartArgs,
 ) -> BotResult<()> {
-    //? Do this better
-    if !(matches!(command.user_id(), Ok(UserId(185773647246524416)))
-        || matches!(command.user_id(), Ok(UserId(219905108316520448)))
-        || matches!(command.user_id(), Ok(UserId(127449164224266240)))
-        || matches!(command.user_id(), Ok(UserId(250998032856776704))))
-    {
-        let builder =
-            MessageBuilder::new().error("You do not have permission to use this command!");
-        return command.create_message(&ctx, builder).await;
-    }
-    info!("Adding map to osuvs queue...");
-    match map_id {
-        Some(id) => {
-            let start_date = ctx.database.get_latest_osuvs_date().await?;
-            let end_date = start_date + Duration::days(7);
-            let _ = ctx
-                .database
-                .insert_osuvs_map(id, start_date, end_date)
-                .await?;
+    if !check_permission(&ctx, &command, PERMISSION_OSUVS_ADMIN).await? {
+        return permission_denied(&ctx, &command).await;
+    }
+    let parsed = parse_rules(&args).and_then(|rules| {
+        let start_date = args.start.as_deref().map(parse_start_date).transpose()?;
+        let duration = args
+            .duration
+            .map_or(Ok(Duration::days(DEFAULT_DURATION_DAYS)), parse_duration)?;
+
+        Ok((rules, start_date, duration))
+    });
+
+    let (rules, start_date, duration) = match parsed {
+        Ok(parsed) => parsed,
+        Err(content) => {
+            let builder = MessageBuilder::new().error(content);
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    let map_id = match args.map_id {
+        Some(map_id) => map_id,
+        None => {
             let builder = MessageBuilder::new()
-                .embed("Added the map! Big chungy boing!")
-                .ephemeral();
-            command.create_message(&ctx, builder).await
+                .error("Please specify a valid map url of a difficulty, not of a mapset!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, command.user_id()?);
+
+    // Downloading the map and calculating pp might take a while
+    command.start_thinking(&ctx).await?;
+
+    let map = match ctx.osu.beatmap().map_id(map_id).await {
+        Ok(map) => map,
+        Err(OsuError::NotFound) => {
+            let content = format!("Could not find a map with id `{}`!", map_id);
+            let builder = MessageBuilder::new().error(content);
+
+            return command.update_message(&ctx, builder).await;
+        }
+        Err(why) => {
+            let builder = MessageBuilder::new().error(OSU_API_ISSUE);
+            let _ = command.update_message(&ctx, builder).await;
+
+            return Err(why.into());
         }
+    };
+
+    let mode = args.mode.unwrap_or(map.mode);
+    let mut mods = preview_mods(&rules);
+    mods.push(GameMods::NoMod);
+    let calculator = PpCalculator::new(&ctx, map_id, map.checksum.as_deref(), mode, &mods).await?;
+
+    if let Err(content) = validate_map(&ctx, &map, mode, &calculator).await? {
+        let builder = MessageBuilder::new().error(content);
+
+        return command.update_message(&ctx, builder).await;
+    }
+
+    if let Some(content) = schedule_conflict(&ctx, mode, start_date, duration).await? {
+        let builder = MessageBuilder::new().error(content);
+
+        return command.update_message(&ctx, builder).await;
+    }
+
+    let schedule = match start_date {
+        Some(start_date) => format!("Starts {}", start_date.format(OSUVS_DATE_FORMAT)),
+        None => format!(
+            "Starts at the end of the queue, currently {}",
+            ctx.database
+                .get_latest_osuvs_date(mode)
+                .await?
+                .format(OSUVS_DATE_FORMAT)
+        ),
+    };
+
+    let description = preview_description(&map, &calculator, &rules, &schedule, duration);
+
+    let components = confirm_buttons();
+    let builder = MessageBuilder::new()
+        .embed(preview_embed(
+            &map,
+            mode,
+            &description,
+            "Add this map to the queue?",
+        ))
+        .components(&components);
+    command.update_message(&ctx, builder).await?;
+
+    let component = match stream.next().await {
+        Some(component) => component,
         None => {
-            let builder = MessageBuilder::new().error("There is currently no ongoing OsuVS!");
-            command.create_message(&ctx, builder).await
+            let embed = preview_embed(&map, mode, &description, "Timed out, the map was not added");
+
+            return command.update_message(&ctx, embed).await;
         }
+    };
+
+    if component.data.custom_id != BUTTON_CONFIRM {
+        let embed = preview_embed(&map, mode, &description, "Cancelled, the map was not added");
+
+        return component.update_message(&ctx, embed).await;
     }
+
+    // The queue might have changed while waiting for the confirmation
+    let conflict = if ctx.database.osuvs_map_exists(map_id).await? {
+        Some("This map has already been used for an OsuVS!".to_owned())
+    } else {
+        schedule_conflict(&ctx, mode, start_date, duration).await?
+    };
+
+    if let Some(content) = conflict {
+        let builder = MessageBuilder::new().error(content);
+
+        return component.update_message(&ctx, builder).await;
+    }
+
+    info!("Adding map {} to osuvs queue...", map_id);
+    let (start_date, end_date) = match start_date {
+        Some(start_date) => {
+            let end_date = start_date + duration;
+
+            ctx.database
+                .insert_osuvs_map(map_id, mode, &rules, start_date, end_date)
+                .await?;
+
+            (start_date, end_date)
+        }
+        None => enqueue_osuvs_map(&ctx, map_id, mode, &rules, duration).await?,
+    };
+
+    let content = format!(
+        "Added the map! Big chungy boing!\nIt runs from {} until {}.",
+        start_date.format(OSUVS_DATE_FORMAT),
+        end_date.format(OSUVS_DATE_FORMAT)
+    );
+    let builder = MessageBuilder::new().embed(content);
+
+    component.update_message(&ctx, builder).await
+}
+
+/// Returns the reason why a round with an explicit start can not be scheduled
+async fn schedule_conflict(
+    ctx: &Context,
+    mode: GameMode,
+    start_date: Option<DateTime<Utc>>,
+    duration: Duration,
+) -> BotResult<Option<String>> {
+    let start_date = match start_date {
+        Some(start_date) => start_date,
+        None => return Ok(None),
+    };
+
+    let overlaps = ctx
+        .database
+        .osuvs_schedule_overlaps(mode, start_date, start_date + duration)
+        .await?;
+
+    let content = overlaps.then(|| {
+        format!(
+            "This would overlap with another {} OsuVS round, \
+            check `/osuvs queue` for a free slot!",
+            mode_name(mode)
+        )
+    });
+
+    Ok(content)
+}
+
+fn preview_description(
+    map: &Beatmap,
+    calculator: &PpCalculator,
+    rules: &OsuVSRules,
+    schedule: &str,
+    duration: Duration,
+) -> String {
+    let days = duration.num_days();
+
+    let mut description = format!(
+        "Stars: `{}★` Length: `{}` (`{}`) Combo: `{}x`\n\
+        CS: `{}` HP: `{}` OD: `{}` AR: `{}`\n\
+        {}\n\
+        {} and runs for {} day{}\n",
+        round(calculator.stars(GameMods::NoMod)),
+        sec_to_minsec(map.seconds_total),
+        sec_to_minsec(map.seconds_drain),
+        map.max_combo.unwrap_or(0),
+        round(map.cs),
+        round(map.hp),
+        round(map.od),
+        round(map.ar),
+        rules,
+        schedule,
+        days,
+        if days == 1 { "" } else { "s" },
+    );
+
+    let mods = preview_mods(rules);
+
+    if mods.is_empty() {
+        description.push_str("No common mod combination is allowed by the rules");
+
+        return description;
+    }
+
+    let _ = write!(description, "```\n{:<8}", "Mods");
+
+    for acc in PREVIEW_ACCURACIES.iter() {
+        let _ = write!(description, "{:>9}", format!("{}%", acc));
+    }
+
+    for mods in mods {
+        let _ = write!(description, "\n{:<8}", mods.to_string());
+
+        for &acc in PREVIEW_ACCURACIES.iter() {
+            let pp = calculator.pp_at_accuracy(mods, acc).pp() as f32;
+            let _ = write!(description, "{:>9}", format!("{}pp", round(pp)));
+        }
+    }
+
+    description.push_str("\n```");
+
+    description
+}
+
+fn preview_embed(map: &Beatmap, mode: GameMode, description: &str, footer: &str) -> Embed {
+    EmbedBuilder::new()
+        .title(map_to_string(map))
+        .url(format!("{}b/{}", OSU_BASE, map.map_id))
+        .author(Author::new(format!("{} OsuVS preview", mode_name(mode))))
+        .description(description)
+        .image(format!(
+            "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
+            map.mapset_id
+        ))
+        .footer(Footer::new(footer))
+        .build()
+}
+
+/// Common mod combinations adjusted to the rules
+fn preview_mods(rules: &OsuVSRules) -> Vec<GameMods> {
+    let combinations = [
+        GameMods::NoMod,
+        GameMods::Hidden,
+        GameMods::HardRock,
+        GameMods::DoubleTime,
+        GameMods::Hidden | GameMods::HardRock,
+        GameMods::Hidden | GameMods::DoubleTime,
+    ];
+
+    let mut mods = Vec::with_capacity(combinations.len());
+
+    for combination in combinations {
+        let combination = combination | rules.required_mods;
+
+        if !combination.intersects(rules.forbidden_mods) && !mods.contains(&combination) {
+            mods.push(combination);
+        }
+    }
+
+    mods
+}
+
+fn confirm_buttons() -> [Component; 1] {
+    let confirm = Button {
+        custom_id: Some(BUTTON_CONFIRM.to_owned()),
+        disabled: false,
+        emoji: None,
+        label: Some("Confirm".to_owned()),
+        style: ButtonStyle::Success,
+        url: None,
+    };
+
+    let cancel = Button {
+        custom_id: Some(BUTTON_CANCEL.to_owned()),
+        disabled: false,
+        emoji: None,
+        label: Some("Cancel".to_owned()),
+        style: ButtonStyle::Danger,
+        url: None,
+    };
+
+    [Component::ActionRow(ActionRow {
+        components: vec![Component::Button(confirm), Component::Button(cancel)],
+    })]
+}
+
+fn parse_rules(args: &StartArgs) -> Result<OsuVSRules, String> {
+    let mut rules = OsuVSRules::default();
+
+    if let Some(mods) = args.required_mods.as_deref() {
+        rules.required_mods =
+            GameMods::from_str(mods).map_err(|_| format!("Failed to parse `{}` as mods!", mods))?;
+    }
+
+    let forbidden_mods = match args.forbidden_mods.as_deref() {
+        Some(mods) if mods.eq_ignore_ascii_case("all") => GameMods::all(),
+        Some(mods) => {
+            GameMods::from_str(mods).map_err(|_| format!("Failed to parse `{}` as mods!", mods))?
+        }
+        None => GameMods::NoMod,
+    };
+
+    // ScoreV2 stays forbidden unless it is explicitly required
+    rules.forbidden_mods = if rules.required_mods.contains(GameMods::ScoreV2) {
+        forbidden_mods
+    } else {
+        forbidden_mods | GameMods::ScoreV2
+    };
+
+    if rules.required_mods.intersects(rules.forbidden_mods) {
+        return Err("Mods can not be both required and forbidden!".to_owned());
+    }
+
+    if let Some(metric) = args.metric {
+        rules.metric = metric;
+    }
+
+    rules.tiebreak = args.tiebreak.filter(|&tiebreak| tiebreak != rules.metric);
+
+    Ok(rules)
 }
@@ -0,0 +1,177 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use rosu_v2::prelude::{GameMode, OsuError, Score};
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    database::OsuVSMap,
+    error::BotResult,
+    utils::{
+        osu::mode_name,
+        osuvs::{calculate_pp, map_title, metric_value, rank_change, submit_score},
+        ApplicationCommandExt, Author, EmbedBuilder, MessageBuilder, OSU_API_ISSUE, OSU_BASE,
+    },
+};
+
+use super::curr_osuvs_map;
+
+pub async fn submit(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    score_id: Option<u64>,
+    mode: Option<GameMode>,
+) -> BotResult<()> {
+    let osuvs_map = match curr_osuvs_map(&ctx, &command, mode).await? {
+        Some(osuvs_map) => osuvs_map,
+        None => return Ok(()),
+    };
+
+    let osu_id = match ctx.database.get_manual_link(command.user_id()?).await? {
+        Some(osu_id) => osu_id,
+        None => {
+            let builder = MessageBuilder::new()
+                .error("You need to link your osu! account before you can submit scores!");
+
+            return command.create_message(&ctx, builder).await;
+        }
+    };
+
+    // Requesting scores and calculating pp might take a while
+    command.start_thinking(&ctx).await?;
+
+    let scores = match submitted_scores(&ctx, &osuvs_map, osu_id, score_id).await {
+        Ok(Ok(scores)) => scores,
+        Ok(Err(content)) => {
+            let builder = MessageBuilder::new().error(content);
+
+            return command.update_message(&ctx, builder).await;
+        }
+        Err(why) => {
+            let builder = MessageBuilder::new().error(OSU_API_ISSUE);
+            let _ = command.update_message(&ctx, builder).await;
+
+            return Err(why.into());
+        }
+    };
+
+    let mut scores: Vec<_> = scores
+        .into_iter()
+        .filter(|score| osuvs_map.accepts(score))
+        .collect();
+
+    if scores.is_empty() {
+        let content = if score_id.is_some() {
+            format!(
+                "This score does not count for the OsuVS! It must be a pass on the map \
+                that was set after the OsuVS started and follows its rules:\n{}",
+                osuvs_map.rules
+            )
+        } else {
+            "None of your recent plays count for the OsuVS!".to_owned()
+        };
+        let builder = MessageBuilder::new().error(content);
+
+        return command.update_message(&ctx, builder).await;
+    }
+
+    let rules = &osuvs_map.rules;
+
+    if rules.uses_pp() {
+        calculate_pp(&ctx, &osuvs_map, scores.iter_mut()).await?;
+    }
+
+    scores.sort_unstable_by(|a, b| rules.cmp(a, b));
+    let score = scores.swap_remove(0);
+
+    let mut highscores = ctx
+        .database
+        .get_osuvs_highscores(osuvs_map.map_id, rules, usize::MAX)
+        .await?;
+
+    let prev_score = highscores
+        .iter()
+        .find(|(user_id, _)| *user_id == osu_id)
+        .map(|(_, score)| score);
+
+    if let Some(prev_score) = prev_score {
+        if prev_score.score_id == score.score_id || rules.cmp(&score, prev_score) != Ordering::Less
+        {
+            let content = format!(
+                "Your current OsuVS score with {} is still your best, nothing changed!",
+                metric_value(rules.metric, prev_score)
+            );
+            let builder = MessageBuilder::new().embed(content);
+
+            return command.update_message(&ctx, builder).await;
+        }
+    }
+
+    info!(
+        "Submitting osuvs score {} of user {}...",
+        score.score_id, osu_id
+    );
+    let value = metric_value(rules.metric, &score);
+    let title = map_title(&ctx, &osuvs_map, &score).await?;
+    let (prev_idx, new_idx) =
+        submit_score(&ctx, &osuvs_map, &mut highscores, osu_id, score).await?;
+    let rank = rank_change(prev_idx, new_idx);
+
+    let description = format!(
+        "Submitted your score with {} on [{}]({}b/{})\n{}",
+        value, title, OSU_BASE, osuvs_map.map_id, rank
+    );
+    let builder = EmbedBuilder::new()
+        .author(Author::new(format!(
+            "{} OsuVS score submitted",
+            mode_name(osuvs_map.mode)
+        )))
+        .description(description);
+
+    command.update_message(&ctx, builder).await
+}
+
+/// Get the scores to consider for the submission. If a score id is given,
+/// it is searched in the user's recent plays and their best score on the map.
+async fn submitted_scores(
+    ctx: &Context,
+    osuvs_map: &OsuVSMap,
+    osu_id: u32,
+    score_id: Option<u64>,
+) -> Result<Result<Vec<Score>, String>, OsuError> {
+    let recent = ctx
+        .osu
+        .user_scores(osu_id)
+        .recent()
+        .mode(osuvs_map.mode)
+        .limit(100)
+        .await?;
+
+    let score_id = match score_id {
+        Some(score_id) => score_id,
+        None if recent.is_empty() => return Ok(Err("You don't have any recent plays!".to_owned())),
+        None => return Ok(Ok(recent)),
+    };
+
+    if let Some(score) = recent.into_iter().find(|score| score.score_id == score_id) {
+        return Ok(Ok(vec![score]));
+    }
+
+    // Scores that are no longer among the recent plays
+    // can only be found if they're the best score on the map
+    let best = ctx
+        .osu
+        .beatmap_user_score(osuvs_map.map_id, osu_id)
+        .mode(osuvs_map.mode)
+        .await;
+
+    match best {
+        Ok(best) if best.score.score_id == score_id => Ok(Ok(vec![best.score])),
+        Ok(_) | Err(OsuError::NotFound) => Ok(Err(format!(
+            "Could not find a score with id `{}` among your recent plays \
+            or your best score on the OsuVS map!",
+            score_id
+        ))),
+        Err(why) => Err(why),
+    }
+}
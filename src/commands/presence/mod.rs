@@ -0,0 +1,5 @@
+mod online;
+mod settings;
+
+pub use online::Online;
+pub use settings::Presence;
@@ -0,0 +1,118 @@
+use std::{fmt::Write, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use twilight_model::application::interaction::ApplicationCommand;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{
+        pagination::{page_buttons, ComponentStream, Pages},
+        ApplicationCommandExt, Author, EmbedBuilder, Footer, MessageBuilder, MessageComponentExt,
+        OSU_BASE,
+    },
+};
+
+const ENTRIES_PER_PAGE: usize = 15;
+
+#[command]
+#[description = "List the linked players that are currently online in osu!"]
+pub struct Online;
+
+struct OnlineEntry {
+    user_id: u32,
+    username: String,
+    since: DateTime<Utc>,
+}
+
+async fn online(ctx: Arc<Context>, command: ApplicationCommand) -> BotResult<()> {
+    if !ctx.irc.is_connected() {
+        let builder = MessageBuilder::new()
+            .error("I'm not connected to osu! chat right now, please try again later!");
+
+        return command.create_message(&ctx, builder).await;
+    }
+
+    let hidden = ctx.database.get_presence_hidden().await?;
+
+    let online: Vec<_> = ctx
+        .irc
+        .targets
+        .iter()
+        .filter_map(|(&user_id, since)| since.map(|since| (user_id, since)))
+        .filter(|(user_id, _)| !hidden.contains(user_id))
+        .collect();
+
+    let mut entries: Vec<_> = online
+        .into_iter()
+        .filter_map(|(user_id, since)| {
+            let username = ctx.links.username(user_id)?;
+
+            Some(OnlineEntry {
+                user_id,
+                username,
+                since,
+            })
+        })
+        .collect();
+
+    // Longest online first
+    entries.sort_unstable_by_key(|entry| entry.since);
+
+    info!("Displaying {} online players...", entries.len());
+    let mut pages = Pages::new(ENTRIES_PER_PAGE, entries.len());
+    let embed = online_embed(&entries, &pages);
+
+    if pages.last_page() == 1 {
+        return command.create_message(&ctx, embed).await;
+    }
+
+    // Start listening before responding so no button press is missed
+    let mut stream = ComponentStream::new(&ctx, command.id, command.user_id()?);
+
+    let components = [page_buttons(&pages)];
+    let builder = MessageBuilder::new().embed(embed).components(&components);
+    command.create_message(&ctx, builder).await?;
+
+    while let Some(component) = stream.next().await {
+        if !pages.update(&component.data.custom_id) {
+            continue;
+        }
+
+        let embed = online_embed(&entries, &pages);
+        let components = [page_buttons(&pages)];
+        let builder = MessageBuilder::new().embed(embed).components(&components);
+        component.update_message(&ctx, builder).await?;
+    }
+
+    // Timed out, remove the buttons
+    let embed = online_embed(&entries, &pages);
+    command.update_message(&ctx, embed).await
+}
+
+fn online_embed(entries: &[OnlineEntry], pages: &Pages) -> EmbedBuilder {
+    let mut description = String::new();
+    let page = entries.iter().skip(pages.index).take(pages.per_page);
+
+    for entry in page {
+        let _ = writeln!(
+            description,
+            "• [{}]({}users/{}) online since <t:{}:R>",
+            entry.username,
+            OSU_BASE,
+            entry.user_id,
+            entry.since.timestamp()
+        );
+    }
+
+    if entries.is_empty() {
+        description.push_str("Nobody is online right now");
+    }
+
+    let author = format!("{} linked players online", entries.len());
+
+    EmbedBuilder::new()
+        .author(Author::new(author))
+        .description(description)
+        .footer(Footer::new(pages.footer_text()))
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use twilight_model::{
+    application::{
+        command::{BaseCommandOptionData, CommandOption, OptionsCommandOptionData},
+        interaction::{
+            application_command::{CommandData, CommandDataOption},
+            ApplicationCommand,
+        },
+    },
+    id::UserId,
+};
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{ApplicationCommandExt, MessageBuilder},
+};
+
+#[command]
+#[args = "PresenceArgs"]
+#[description = "Manage notifications about players coming online in osu!"]
+#[options = "presence_options"]
+pub struct Presence;
+
+pub enum PresenceArgs {
+    Follow(UserId),
+    Unfollow(UserId),
+    Visibility(bool),
+}
+
+impl PresenceArgs {
+    async fn parse_options(_: Arc<Context>, data: CommandData) -> BotResult<Self> {
+        for option in data.options {
+            if let CommandDataOption::SubCommand { name, options } = option {
+                for option in options {
+                    match (name.as_str(), option) {
+                        ("follow", CommandDataOption::String { value, .. }) => {
+                            if let Ok(id) = value.parse() {
+                                return Ok(Self::Follow(UserId(id)));
+                            }
+                        }
+                        ("unfollow", CommandDataOption::String { value, .. }) => {
+                            if let Ok(id) = value.parse() {
+                                return Ok(Self::Unfollow(UserId(id)));
+                            }
+                        }
+                        ("visibility", CommandDataOption::Boolean { value, .. }) => {
+                            return Ok(Self::Visibility(value))
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        unreachable!();
+    }
+}
+
+fn presence_options() -> Vec<CommandOption> {
+    let user_option = |description: &str| {
+        CommandOption::User(BaseCommandOptionData {
+            description: description.to_string(),
+            name: "user".to_string(),
+            required: true,
+        })
+    };
+
+    let follow = OptionsCommandOptionData {
+        description: "Get a DM whenever the user comes online in osu!".to_string(),
+        name: "follow".to_string(),
+        options: vec![user_option("Specify the user to follow")],
+        required: false,
+    };
+
+    let unfollow = OptionsCommandOptionData {
+        description: "Stop getting DMs when the user comes online".to_string(),
+        name: "unfollow".to_string(),
+        options: vec![user_option("Specify the user to unfollow")],
+        required: false,
+    };
+
+    let visible_option = BaseCommandOptionData {
+        description: "Choose whether others can see when you are online".to_string(),
+        name: "visible".to_string(),
+        required: true,
+    };
+
+    let visibility = OptionsCommandOptionData {
+        description: "Hide or show your osu! presence in /online and notifications".to_string(),
+        name: "visibility".to_string(),
+        options: vec![CommandOption::Boolean(visible_option)],
+        required: false,
+    };
+
+    vec![
+        CommandOption::SubCommand(follow),
+        CommandOption::SubCommand(unfollow),
+        CommandOption::SubCommand(visibility),
+    ]
+}
+
+async fn presence(
+    ctx: Arc<Context>,
+    command: ApplicationCommand,
+    args: PresenceArgs,
+) -> BotResult<()> {
+    let user_id = command.user_id()?;
+
+    let builder = match args {
+        PresenceArgs::Follow(target) if target == user_id => {
+            MessageBuilder::new().error("You can not follow yourself!")
+        }
+        PresenceArgs::Follow(target) => {
+            let hidden = match ctx.database.get_manual_link(target).await? {
+                Some(osu_id) => ctx.database.get_presence_hidden().await?.contains(&osu_id),
+                None => {
+                    let builder = MessageBuilder::new()
+                        .error("That user has not linked their osu! account!")
+                        .ephemeral();
+
+                    return command.create_message(&ctx, builder).await;
+                }
+            };
+
+            if hidden {
+                MessageBuilder::new().error("That user hides their osu! presence!")
+            } else {
+                ctx.database.insert_presence_follow(user_id, target).await?;
+                let content = format!(
+                    "You will now receive a DM whenever <@{}> comes online",
+                    target
+                );
+
+                MessageBuilder::new().embed(content)
+            }
+        }
+        PresenceArgs::Unfollow(target) => {
+            if ctx.database.remove_presence_follow(user_id, target).await? {
+                let content = format!(
+                    "You will no longer receive DMs when <@{}> comes online",
+                    target
+                );
+
+                MessageBuilder::new().embed(content)
+            } else {
+                MessageBuilder::new().error("You are not following that user!")
+            }
+        }
+        PresenceArgs::Visibility(true) => {
+            ctx.database.remove_presence_hidden(user_id).await?;
+
+            MessageBuilder::new().embed("Others can now see when you are online in osu!")
+        }
+        PresenceArgs::Visibility(false) => {
+            ctx.database.insert_presence_hidden(user_id).await?;
+
+            MessageBuilder::new()
+                .embed("Your osu! presence is now hidden from `/online` and notifications")
+        }
+    };
+
+    command.create_message(&ctx, builder.ephemeral()).await
+}
@@ -1,14 +1,19 @@
 use std::sync::Arc;
 
-use crate::{osu_irc::IrcClient, stats::BotStats};
+use crate::{
+    beatmap_store::BeatmapStore, difficulty_cache::DifficultyCache, link_manager::LinkManager,
+    osu_irc::IrcClient, stats::BotStats,
+};
 use crate::{BotResult, Database};
 
 use rosu_v2::Osu as OsuClient;
 use twilight_cache_inmemory::InMemoryCache;
 use twilight_gateway::Cluster;
 use twilight_http::Client as HttpClient;
+use twilight_model::channel::embed::Embed;
 use twilight_model::gateway::payload::UpdatePresence;
 use twilight_model::gateway::presence::{Activity, ActivityType, Status};
+use twilight_model::id::{ChannelId, RoleId, UserId};
 use twilight_standby::Standby;
 
 pub struct Context {
@@ -16,10 +21,17 @@ pub struct Context {
     pub database: Database,
     pub osu: OsuClient,
     pub irc: Arc<IrcClient>,
+    pub links: LinkManager,
     pub cluster: Cluster,
     pub http: HttpClient,
     pub standby: Standby,
     pub stats: BotStats,
+    pub beatmap_store: BeatmapStore,
+    pub difficulty_cache: DifficultyCache,
+    /// Role given to the winners of the latest osuvs of each mode, if configured
+    pub champion_role: Option<RoleId>,
+    /// Channel to announce when linked players come online, if configured
+    pub online_feed: Option<ChannelId>,
 }
 
 impl Context {
@@ -36,6 +48,56 @@ impl Context {
 
         Ok(())
     }
+
+    pub async fn send_dm(&self, user_id: UserId, embed: Embed) -> BotResult<()> {
+        let channel = self
+            .http
+            .create_private_channel(user_id)
+            .exec()
+            .await?
+            .model()
+            .await?;
+
+        self.http
+            .create_message(channel.id)
+            .embeds(&[embed])?
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Link the discord user to the osu! user and start tracking them on IRC.
+    /// Returns `false` if the osu! user is already linked to someone else.
+    pub async fn link_user(&self, discord_id: UserId, osu_id: u32) -> BotResult<bool> {
+        let prev_osu_id = self.database.get_manual_link(discord_id).await?;
+
+        if !self.database.insert_manual_link(discord_id, osu_id).await? {
+            return Ok(false);
+        }
+
+        if let Some(prev_osu_id) = prev_osu_id.filter(|&prev| prev != osu_id) {
+            self.links.untrack(&self.irc, prev_osu_id);
+        }
+
+        if let Err(why) = self.links.track(&self.osu, &self.irc, osu_id).await {
+            unwind_error!(warn, why, "Failed to track newly linked user on IRC: {}");
+        }
+
+        Ok(true)
+    }
+
+    /// Unlink the discord user and stop tracking their osu! user on IRC.
+    /// Returns the osu! id that was linked.
+    pub async fn unlink_user(&self, discord_id: UserId) -> BotResult<Option<u32>> {
+        let osu_id = self.database.remove_manual_link(discord_id).await?;
+
+        if let Some(osu_id) = osu_id {
+            self.links.untrack(&self.irc, osu_id);
+        }
+
+        Ok(osu_id)
+    }
 }
 
 pub fn generate_activity(activity_type: ActivityType, message: String) -> Activity {
@@ -0,0 +1,74 @@
+use rosu_pp::DifficultyAttributes;
+use rosu_v2::prelude::{GameMode, GameMods};
+
+use crate::{
+    database::{models::StoredDifficultyAttributes, Database},
+    error::BotResult,
+};
+
+impl Database {
+    /// Difficulty attributes that were calculated on the version of the map with the checksum
+    pub async fn get_difficulty_attributes(
+        &self,
+        map_id: u32,
+        mods: GameMods,
+        mode: GameMode,
+        checksum: &str,
+    ) -> BotResult<Option<DifficultyAttributes>> {
+        let query = sqlx::query!(
+            "SELECT attributes FROM difficulty_attributes \
+            WHERE map_id = $1 AND mods = $2 AND mode = $3 AND checksum = $4;",
+            map_id as i32,
+            mods.bits() as i32,
+            mode as i16,
+            checksum
+        );
+
+        match query.fetch_optional(&self.pool).await? {
+            Some(row) => {
+                let StoredDifficultyAttributes(attributes) =
+                    serde_json::from_value(row.attributes)?;
+
+                Ok(Some(attributes))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn insert_difficulty_attributes(
+        &self,
+        map_id: u32,
+        mods: GameMods,
+        mode: GameMode,
+        checksum: &str,
+        attributes: DifficultyAttributes,
+    ) -> BotResult<()> {
+        let query = sqlx::query!(
+            "INSERT INTO difficulty_attributes (map_id, mods, mode, checksum, attributes) \
+            VALUES ($1, $2, $3, $4, $5) ON CONFLICT (map_id, mods, mode) \
+            DO UPDATE SET checksum = $4, attributes = $5;",
+            map_id as i32,
+            mods.bits() as i32,
+            mode as i16,
+            checksum,
+            serde_json::to_value(StoredDifficultyAttributes(attributes))?
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Remove the attributes of the map that were calculated on a different version of it
+    pub async fn remove_stale_difficulty_attributes(
+        &self,
+        map_id: u32,
+        checksum: &str,
+    ) -> BotResult<()> {
+        let query = sqlx::query!(
+            "DELETE FROM difficulty_attributes WHERE map_id = $1 AND checksum <> $2;",
+            map_id as i32,
+            checksum
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,14 @@
 use crate::{database::Database, error::BotResult};
 
+use chrono::{DateTime, Utc};
 use futures::StreamExt;
 use hashbrown::HashMap;
+use sqlx::Error as SqlError;
 use twilight_model::id::UserId;
 
+/// Postgres error code for violated unique constraints
+const UNIQUE_VIOLATION: &str = "23505";
+
 impl Database {
     pub async fn get_manual_links(&self) -> BotResult<HashMap<UserId, u32>> {
         let mut stream = sqlx::query!("SELECT * FROM manual_links;").fetch(&self.pool);
@@ -15,4 +20,101 @@ impl Database {
         }
         Ok(manual_links)
     }
+
+    pub async fn get_manual_link(&self, discord_id: UserId) -> BotResult<Option<u32>> {
+        let query = sqlx::query!(
+            "SELECT osu_id FROM manual_links WHERE discord_id = $1;",
+            discord_id.0 as i64
+        );
+        let result = query.fetch_optional(&self.pool).await?;
+        Ok(result.map(|entry| entry.osu_id as u32))
+    }
+
+    /// Discord user that the osu! account is linked to
+    pub async fn get_manual_link_by_osu_id(&self, osu_id: u32) -> BotResult<Option<UserId>> {
+        let query = sqlx::query!(
+            "SELECT discord_id FROM manual_links WHERE osu_id = $1;",
+            osu_id as i64
+        );
+        let result = query.fetch_optional(&self.pool).await?;
+        Ok(result.map(|entry| UserId(entry.discord_id as u64)))
+    }
+
+    /// Returns `false` if the osu! account is already linked to another discord user
+    pub async fn insert_manual_link(&self, discord_id: UserId, osu_id: u32) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "INSERT INTO manual_links (discord_id, osu_id) SELECT $1, $2 \
+            WHERE NOT EXISTS (SELECT 1 FROM manual_links WHERE osu_id = $2 AND discord_id != $1) \
+            ON CONFLICT (discord_id) DO UPDATE SET osu_id = $2;",
+            discord_id.0 as i64,
+            osu_id as i64
+        );
+
+        match query.execute(&self.pool).await {
+            Ok(result) => Ok(result.rows_affected() == 1),
+            // Someone else linked the osu! account concurrently
+            Err(SqlError::Database(why)) if why.code().as_deref() == Some(UNIQUE_VIOLATION) => {
+                Ok(false)
+            }
+            Err(why) => Err(why.into()),
+        }
+    }
+
+    /// Returns the osu! id that was linked to the discord user
+    pub async fn remove_manual_link(&self, discord_id: UserId) -> BotResult<Option<u32>> {
+        let query = sqlx::query!(
+            "DELETE FROM manual_links WHERE discord_id = $1 RETURNING osu_id;",
+            discord_id.0 as i64
+        );
+        let result = query.fetch_optional(&self.pool).await?;
+        Ok(result.map(|entry| entry.osu_id as u32))
+    }
+
+    /// Replaces any previous code of the discord user and removes expired codes
+    pub async fn insert_link_code(
+        &self,
+        discord_id: UserId,
+        osu_id: u32,
+        code: &str,
+        expires_at: DateTime<Utc>,
+    ) -> BotResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("DELETE FROM link_codes WHERE expires_at < now();")
+            .execute(&mut tx)
+            .await?;
+
+        sqlx::query!(
+            "INSERT INTO link_codes (discord_id, osu_id, code, expires_at) VALUES ($1, $2, $3, $4) \
+            ON CONFLICT (discord_id) DO UPDATE SET osu_id = $2, code = $3, expires_at = $4;",
+            discord_id.0 as i64,
+            osu_id as i64,
+            code,
+            expires_at
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// The osu! id and code that the discord user has been sent, unless it expired
+    pub async fn get_link_code(&self, discord_id: UserId) -> BotResult<Option<(u32, String)>> {
+        let query = sqlx::query!(
+            "SELECT osu_id, code FROM link_codes WHERE discord_id = $1 AND expires_at > now();",
+            discord_id.0 as i64
+        );
+        let result = query.fetch_optional(&self.pool).await?;
+        Ok(result.map(|entry| (entry.osu_id as u32, entry.code)))
+    }
+
+    pub async fn remove_link_code(&self, discord_id: UserId) -> BotResult<()> {
+        let query = sqlx::query!(
+            "DELETE FROM link_codes WHERE discord_id = $1;",
+            discord_id.0 as i64
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
 }
@@ -1,4 +1,14 @@
+mod difficulty_attributes;
 mod manual_links;
 mod messages;
-mod unchecked_members;
 mod osuvs;
+mod osuvs_announcements;
+mod osuvs_bounds;
+mod osuvs_divisions;
+mod osuvs_notifications;
+mod osuvs_requests;
+mod osuvs_seasons;
+mod osuvs_tracking;
+mod permissions;
+mod presence;
+mod unchecked_members;
@@ -1,59 +1,209 @@
 use chrono::{DateTime, Utc};
 use futures::StreamExt;
 use hashbrown::HashMap;
-use rosu_v2::prelude::Score;
+use rosu_v2::prelude::{GameMode, GameMods, Score};
 
-use crate::{database::Database, error::BotResult};
+use crate::{
+    database::{Database, HallOfFameEntry, OsuVSMap, OsuVSMetric, OsuVSPlacement, OsuVSRules},
+    error::BotResult,
+};
+
+pub(super) struct OsuVSMapRow {
+    pub(super) beatmap_id: i32,
+    pub(super) start_date: DateTime<Utc>,
+    pub(super) end_date: DateTime<Utc>,
+    pub(super) mode: i16,
+    pub(super) required_mods: i32,
+    pub(super) forbidden_mods: i32,
+    pub(super) metric: i16,
+    pub(super) tiebreak: Option<i16>,
+}
+
+impl From<OsuVSMapRow> for OsuVSMap {
+    fn from(row: OsuVSMapRow) -> Self {
+        Self {
+            map_id: row.beatmap_id as u32,
+            mode: GameMode::from(row.mode as u8),
+            rules: OsuVSRules {
+                required_mods: GameMods::from_bits_truncate(row.required_mods as u32),
+                forbidden_mods: GameMods::from_bits_truncate(row.forbidden_mods as u32),
+                metric: OsuVSMetric::from_i16(row.metric),
+                tiebreak: row.tiebreak.map(OsuVSMetric::from_i16),
+            },
+            start_date: row.start_date,
+            end_date: row.end_date,
+        }
+    }
+}
 
 impl Database {
-    pub async fn get_curr_osuvs_map(&self) -> Option<(u32, DateTime<Utc>, DateTime<Utc>)> {
-        sqlx::query!("SELECT * FROM osuvs_maps WHERE start_date < now() AND end_date > now();")
-            .fetch_optional(&self.pool)
-            .await
-            .map_or_else(
-                |why| {
-                    unwind_error!(warn, why, "Couldn't retrieve osuvs map: {}");
-                    None
-                },
-                |result| {
-                    result.map(|r| {
-                        (
-                            r.beatmap_id as u32,
-                            r.start_date as chrono::DateTime<Utc>,
-                            r.end_date as chrono::DateTime<Utc>,
-                        )
-                    })
-                },
-            )
+    /// All currently running osuvs maps, at most one per mode
+    pub async fn get_curr_osuvs_maps(&self) -> Vec<OsuVSMap> {
+        sqlx::query_as!(
+            OsuVSMapRow,
+            "SELECT * FROM osuvs_maps WHERE start_date < now() AND end_date > now() ORDER BY mode;"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_or_else(
+            |why| {
+                unwind_error!(warn, why, "Couldn't retrieve osuvs maps: {}");
+                Vec::new()
+            },
+            |rows| rows.into_iter().map(OsuVSMap::from).collect(),
+        )
+    }
+
+    /// All running and upcoming osuvs maps, ordered by mode and start date
+    pub async fn get_upcoming_osuvs_maps(&self) -> BotResult<Vec<OsuVSMap>> {
+        let rows = sqlx::query_as!(
+            OsuVSMapRow,
+            "SELECT * FROM osuvs_maps WHERE end_date > now() ORDER BY mode, start_date;"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(OsuVSMap::from).collect())
+    }
+
+    /// All finished osuvs maps, most recent first
+    pub async fn get_past_osuvs_maps(&self) -> BotResult<Vec<OsuVSMap>> {
+        let rows = sqlx::query_as!(
+            OsuVSMapRow,
+            "SELECT * FROM osuvs_maps WHERE end_date < now() ORDER BY end_date DESC, mode;"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(OsuVSMap::from).collect())
+    }
+
+    pub async fn get_osuvs_map(&self, map_id: u32) -> BotResult<Option<OsuVSMap>> {
+        let row = sqlx::query_as!(
+            OsuVSMapRow,
+            "SELECT * FROM osuvs_maps WHERE beatmap_id = $1;",
+            map_id as i32
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(OsuVSMap::from))
+    }
+
+    /// All osuvs maps that were running at the given date
+    pub async fn get_osuvs_maps_at(&self, date: DateTime<Utc>) -> BotResult<Vec<OsuVSMap>> {
+        let rows = sqlx::query_as!(
+            OsuVSMapRow,
+            "SELECT * FROM osuvs_maps WHERE start_date <= $1 AND end_date > $1 ORDER BY mode;",
+            date
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(OsuVSMap::from).collect())
     }
 
-    // Return a HashMap mapping user ids to HashMaps mapping mods to scores
-    pub async fn get_osuvs_highscores(&self, count: usize) -> BotResult<Vec<(u32, Score)>> {
-        let map_id = self.get_curr_osuvs_map().await.map(|t| t.0);
-        match map_id {
-            Some(id) => {
-                let mut stream = sqlx::query!(
-                    "SELECT user_id, score FROM osuvs_scores WHERE beatmap_id = $1;",
-                    id as i32
-                )
-                .fetch(&self.pool);
-                let mut scores: HashMap<u32, Score> = HashMap::new();
-                while let Some(r) = stream.next().await.transpose()? {
-                    let (user_id, score) = (r.user_id as u32, r.score);
-
-                    scores.insert(user_id, serde_json::from_value(score)?);
-                }
-                let mut highscores: Vec<_> = scores.into_iter().collect();
-                highscores.sort_unstable_by(|(_, s1), (_, s2)| {
-                    s2.score
-                        .cmp(&s1.score)
-                        .then_with(|| s1.created_at.cmp(&s2.created_at))
-                });
-                highscores.truncate(count);
-                Ok(highscores)
-            }
-            None => Ok(Vec::new()),
+    // Return the best scores on the map, sorted according to the rules
+    pub async fn get_osuvs_highscores(
+        &self,
+        map_id: u32,
+        rules: &OsuVSRules,
+        count: usize,
+    ) -> BotResult<Vec<(u32, Score)>> {
+        let mut stream = sqlx::query!(
+            "SELECT user_id, score FROM osuvs_scores WHERE beatmap_id = $1;",
+            map_id as i32
+        )
+        .fetch(&self.pool);
+        let mut scores: HashMap<u32, Score> = HashMap::new();
+        while let Some(r) = stream.next().await.transpose()? {
+            let (user_id, score) = (r.user_id as u32, r.score);
+
+            scores.insert(user_id, serde_json::from_value(score)?);
+        }
+        let mut highscores: Vec<_> = scores.into_iter().collect();
+        highscores.sort_unstable_by(|(_, s1), (_, s2)| rules.cmp(s1, s2));
+        highscores.truncate(count);
+        Ok(highscores)
+    }
+
+    /// The placement of the player in every finished round of the mode ordered
+    /// by start date. Rounds in which the player did not submit a score are `None`.
+    pub async fn get_osuvs_career(
+        &self,
+        mode: GameMode,
+        user_id: u32,
+    ) -> BotResult<Vec<Option<OsuVSPlacement>>> {
+        let mut stream = sqlx::query!(
+            "SELECT m.beatmap_id, p.placement, p.score->'user'->>'username' AS username, \
+            (p.score->>'mods')::INT4 AS mods \
+            FROM osuvs_maps AS m LEFT JOIN osuvs_placements AS p \
+            ON p.beatmap_id = m.beatmap_id AND p.user_id = $2 \
+            WHERE m.end_date < now() AND m.mode = $1 \
+            ORDER BY m.start_date, m.beatmap_id;",
+            mode as i16,
+            user_id as i32
+        )
+        .fetch(&self.pool);
+
+        let mut rounds = Vec::new();
+
+        while let Some(r) = stream.next().await.transpose()? {
+            let placement = r.placement.map(|placement| OsuVSPlacement {
+                map_id: r.beatmap_id as u32,
+                placement: placement as usize - 1,
+                username: r.username,
+                mods: GameMods::from_bits_truncate(r.mods.unwrap_or(0) as u32),
+            });
+
+            rounds.push(placement);
         }
+
+        Ok(rounds)
+    }
+
+    /// Wins, podiums, and participations of all players in finished rounds
+    /// of the mode, ordered by wins, then podiums, then participations
+    pub async fn get_osuvs_hall_of_fame(&self, mode: GameMode) -> BotResult<Vec<HallOfFameEntry>> {
+        let mut stream = sqlx::query!(
+            "SELECT p.user_id AS \"user_id!\", \
+            (ARRAY_AGG(p.score->'user'->>'username' ORDER BY m.start_date DESC) \
+            FILTER (WHERE p.score->'user' IS NOT NULL))[1] AS username, \
+            COUNT(*) FILTER (WHERE p.placement = 1) AS \"wins!\", \
+            COUNT(*) FILTER (WHERE p.placement <= 3) AS \"podiums!\", \
+            COUNT(*) AS \"rounds!\" \
+            FROM osuvs_placements AS p JOIN osuvs_maps AS m ON m.beatmap_id = p.beatmap_id \
+            WHERE m.end_date < now() AND m.mode = $1 \
+            GROUP BY p.user_id \
+            ORDER BY 3 DESC, 4 DESC, 5 DESC, 2, 1;",
+            mode as i16
+        )
+        .fetch(&self.pool);
+
+        let mut entries = Vec::new();
+
+        while let Some(r) = stream.next().await.transpose()? {
+            let user_id = r.user_id as u32;
+
+            entries.push(HallOfFameEntry {
+                user_id,
+                username: r.username.unwrap_or_else(|| user_id.to_string()),
+                wins: r.wins as usize,
+                podiums: r.podiums as usize,
+                rounds: r.rounds as usize,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    pub async fn get_finished_osuvs_round_count(&self, mode: GameMode) -> BotResult<usize> {
+        let query = sqlx::query!(
+            "SELECT COUNT(*) AS \"count!\" FROM osuvs_maps WHERE end_date < now() AND mode = $1;",
+            mode as i16
+        );
+        let count = query.fetch_one(&self.pool).await?.count;
+        Ok(count as usize)
     }
 
     //? Do I need bool return type
@@ -77,12 +227,20 @@ impl Database {
     pub async fn insert_osuvs_map(
         &self,
         map_id: u32,
+        mode: GameMode,
+        rules: &OsuVSRules,
         start_date: DateTime<Utc>,
         end_date: DateTime<Utc>,
     ) -> BotResult<bool> {
         let query = sqlx::query!(
-            "INSERT INTO osuvs_maps (beatmap_id, start_date, end_date) VALUES ($1, $2, $3) ON CONFLICT (beatmap_id, start_date, end_date) DO NOTHING;",
+            "INSERT INTO osuvs_maps (beatmap_id, mode, required_mods, forbidden_mods, metric, tiebreak, start_date, end_date) \
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (beatmap_id, start_date, end_date) DO NOTHING;",
             map_id as i32,
+            mode as i16,
+            rules.required_mods.bits() as i32,
+            rules.forbidden_mods.bits() as i32,
+            rules.metric as i16,
+            rules.tiebreak.map(|tiebreak| tiebreak as i16),
             start_date,
             end_date
         );
@@ -90,15 +248,113 @@ impl Database {
         Ok(result.rows_affected() == 1)
     }
 
-    pub async fn get_latest_osuvs_date(&self) -> BotResult<DateTime<Utc>> {
+    /// End date of the last queued osuvs map of the mode or now if there is none
+    pub async fn get_latest_osuvs_date(&self, mode: GameMode) -> BotResult<DateTime<Utc>> {
         let now = Utc::now();
-        sqlx::query!("SELECT end_date FROM osuvs_maps ORDER BY end_date DESC LIMIT 1")
-            .fetch_optional(&self.pool)
-            .await
-            .map(|r_opt| match r_opt {
-                Some(r) => r.end_date.max(now),
-                None => now,
-            })
-            .map_err(From::from)
+        sqlx::query!(
+            "SELECT end_date FROM osuvs_maps WHERE mode = $1 ORDER BY end_date DESC LIMIT 1",
+            mode as i16
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map(|r_opt| match r_opt {
+            Some(r) => r.end_date.max(now),
+            None => now,
+        })
+        .map_err(From::from)
+    }
+
+    pub async fn osuvs_map_exists(&self, map_id: u32) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "SELECT EXISTS (SELECT 1 FROM osuvs_maps WHERE beatmap_id = $1) AS \"exists!\";",
+            map_id as i32
+        );
+        let result = query.fetch_one(&self.pool).await?;
+        Ok(result.exists)
+    }
+
+    /// Check whether any osuvs map of the mode overlaps with the given date range
+    pub async fn osuvs_schedule_overlaps(
+        &self,
+        mode: GameMode,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "SELECT EXISTS (SELECT 1 FROM osuvs_maps WHERE mode = $1 AND start_date < $3 AND end_date > $2) AS \"exists!\";",
+            mode as i16,
+            start_date,
+            end_date
+        );
+        let result = query.fetch_one(&self.pool).await?;
+        Ok(result.exists)
+    }
+
+    pub async fn osuvs_score_count(&self, map_id: u32) -> BotResult<usize> {
+        let query = sqlx::query!(
+            "SELECT COUNT(*) AS \"count!\" FROM osuvs_scores WHERE beatmap_id = $1;",
+            map_id as i32
+        );
+        let result = query.fetch_one(&self.pool).await?;
+        Ok(result.count as usize)
+    }
+
+    /// Remove an osuvs map that has no scores yet
+    pub async fn remove_osuvs_map(&self, map_id: u32) -> BotResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM osuvs_announcements WHERE beatmap_id = $1;",
+            map_id as i32
+        )
+        .execute(&mut tx)
+        .await?;
+
+        // Divisions are snapshotted once the round started
+        sqlx::query!(
+            "DELETE FROM osuvs_division_members WHERE beatmap_id = $1;",
+            map_id as i32
+        )
+        .execute(&mut tx)
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM osuvs_map_divisions WHERE beatmap_id = $1;",
+            map_id as i32
+        )
+        .execute(&mut tx)
+        .await?;
+
+        let result = sqlx::query!(
+            "DELETE FROM osuvs_maps WHERE beatmap_id = $1;",
+            map_id as i32
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Move the osuvs maps to their new dates, either all of them or none
+    pub async fn update_osuvs_map_dates(
+        &self,
+        dates: &[(u32, DateTime<Utc>, DateTime<Utc>)],
+    ) -> BotResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for &(map_id, start_date, end_date) in dates {
+            sqlx::query!(
+                "UPDATE osuvs_maps SET start_date = $2, end_date = $3 WHERE beatmap_id = $1;",
+                map_id as i32,
+                start_date,
+                end_date
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
     }
 }
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    database::{Database, OsuVSAnnouncement, OsuVSMap},
+    error::BotResult,
+};
+
+use super::osuvs::OsuVSMapRow;
+
+impl Database {
+    /// Claim the announcement so it is pending until it is marked as sent.
+    /// Returns `false` if it was already claimed.
+    pub async fn claim_osuvs_announcement(
+        &self,
+        map_id: u32,
+        kind: OsuVSAnnouncement,
+    ) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "INSERT INTO osuvs_announcements (beatmap_id, kind) VALUES ($1, $2) \
+            ON CONFLICT DO NOTHING;",
+            map_id as i32,
+            kind as i16
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Mark a claimed announcement as sent successfully
+    pub async fn mark_osuvs_announcement_sent(
+        &self,
+        map_id: u32,
+        kind: OsuVSAnnouncement,
+    ) -> BotResult<()> {
+        let query = sqlx::query!(
+            "UPDATE osuvs_announcements SET sent_at = CURRENT_TIMESTAMP \
+            WHERE beatmap_id = $1 AND kind = $2;",
+            map_id as i32,
+            kind as i16
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Undo a claim so the announcement is attempted again e.g. after sending failed
+    pub async fn release_osuvs_announcement(
+        &self,
+        map_id: u32,
+        kind: OsuVSAnnouncement,
+    ) -> BotResult<()> {
+        let query = sqlx::query!(
+            "DELETE FROM osuvs_announcements WHERE beatmap_id = $1 AND kind = $2;",
+            map_id as i32,
+            kind as i16
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Release all claims that were never marked as sent e.g. because the bot
+    /// stopped while sending them. Returns how many claims were released.
+    pub async fn release_pending_osuvs_announcements(&self) -> BotResult<u64> {
+        let query = sqlx::query!("DELETE FROM osuvs_announcements WHERE sent_at IS NULL;");
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Osuvs maps that started (or ended) until the given date
+    /// but whose announcement has not been sent yet, oldest first
+    pub async fn get_unannounced_osuvs_maps(
+        &self,
+        kind: OsuVSAnnouncement,
+        until: DateTime<Utc>,
+    ) -> BotResult<Vec<OsuVSMap>> {
+        let rows = match kind {
+            OsuVSAnnouncement::Start => {
+                sqlx::query_as!(
+                    OsuVSMapRow,
+                    "SELECT * FROM osuvs_maps m WHERE start_date <= $1 AND NOT EXISTS \
+                    (SELECT 1 FROM osuvs_announcements a WHERE a.beatmap_id = m.beatmap_id \
+                    AND a.kind = $2) ORDER BY start_date, mode;",
+                    until,
+                    kind as i16
+                )
+                .fetch_all(&self.pool)
+                .await?
+            }
+            OsuVSAnnouncement::End => {
+                sqlx::query_as!(
+                    OsuVSMapRow,
+                    "SELECT * FROM osuvs_maps m WHERE end_date <= $1 AND NOT EXISTS \
+                    (SELECT 1 FROM osuvs_announcements a WHERE a.beatmap_id = m.beatmap_id \
+                    AND a.kind = $2) ORDER BY end_date, mode;",
+                    until,
+                    kind as i16
+                )
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(rows.into_iter().map(OsuVSMap::from).collect())
+    }
+}
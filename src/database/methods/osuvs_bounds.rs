@@ -0,0 +1,46 @@
+use rosu_v2::prelude::GameMode;
+
+use crate::{
+    database::{Database, OsuVSMapBounds},
+    error::BotResult,
+};
+
+impl Database {
+    pub async fn get_osuvs_map_bounds(&self, mode: GameMode) -> BotResult<OsuVSMapBounds> {
+        let query = sqlx::query!(
+            "SELECT min_stars, max_stars, min_length, max_length FROM osuvs_map_bounds WHERE mode = $1;",
+            mode as i16
+        );
+        let bounds = query
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|row| OsuVSMapBounds {
+                min_stars: row.min_stars,
+                max_stars: row.max_stars,
+                min_length: row.min_length.map(|length| length as u32),
+                max_length: row.max_length.map(|length| length as u32),
+            })
+            .unwrap_or_default();
+
+        Ok(bounds)
+    }
+
+    pub async fn set_osuvs_map_bounds(
+        &self,
+        mode: GameMode,
+        bounds: &OsuVSMapBounds,
+    ) -> BotResult<()> {
+        let query = sqlx::query!(
+            "INSERT INTO osuvs_map_bounds (mode, min_stars, max_stars, min_length, max_length) \
+            VALUES ($1, $2, $3, $4, $5) ON CONFLICT (mode) DO UPDATE SET min_stars = $2, \
+            max_stars = $3, min_length = $4, max_length = $5;",
+            mode as i16,
+            bounds.min_stars,
+            bounds.max_stars,
+            bounds.min_length.map(|length| length as i32),
+            bounds.max_length.map(|length| length as i32)
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use rosu_v2::prelude::GameMode;
+
+use crate::{
+    database::{Database, OsuVSDivisionMetric, OsuVSDivisions},
+    error::BotResult,
+};
+
+impl Database {
+    /// Divisions that new rounds of the mode are split into
+    pub async fn get_osuvs_division_config(&self, mode: GameMode) -> BotResult<OsuVSDivisions> {
+        let query = sqlx::query!(
+            "SELECT metric, thresholds FROM osuvs_division_config WHERE mode = $1;",
+            mode as i16
+        );
+        let divisions = query
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|row| divisions_from_row(row.metric, row.thresholds))
+            .unwrap_or_else(|| OsuVSDivisions::new(OsuVSDivisionMetric::Rank, Vec::new()));
+
+        Ok(divisions)
+    }
+
+    pub async fn set_osuvs_division_config(
+        &self,
+        mode: GameMode,
+        divisions: &OsuVSDivisions,
+    ) -> BotResult<()> {
+        let thresholds: Vec<_> = divisions.thresholds.iter().map(|&t| t as i32).collect();
+
+        let query = sqlx::query!(
+            "INSERT INTO osuvs_division_config (mode, metric, thresholds) VALUES ($1, $2, $3) \
+            ON CONFLICT (mode) DO UPDATE SET metric = $2, thresholds = $3;",
+            mode as i16,
+            divisions.metric as i16,
+            &thresholds
+        );
+        query.execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Divisions that were snapshotted when the round started
+    pub async fn get_osuvs_map_divisions(&self, map_id: u32) -> BotResult<Option<OsuVSDivisions>> {
+        let query = sqlx::query!(
+            "SELECT metric, thresholds FROM osuvs_map_divisions WHERE beatmap_id = $1;",
+            map_id as i32
+        );
+        let divisions = query
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|row| divisions_from_row(row.metric, row.thresholds));
+
+        Ok(divisions)
+    }
+
+    /// Returns `false` if the round already had divisions
+    pub async fn insert_osuvs_map_divisions(
+        &self,
+        map_id: u32,
+        divisions: &OsuVSDivisions,
+    ) -> BotResult<bool> {
+        let thresholds: Vec<_> = divisions.thresholds.iter().map(|&t| t as i32).collect();
+
+        let query = sqlx::query!(
+            "INSERT INTO osuvs_map_divisions (beatmap_id, metric, thresholds) VALUES ($1, $2, $3) \
+            ON CONFLICT DO NOTHING;",
+            map_id as i32,
+            divisions.metric as i16,
+            &thresholds
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Division of each player of the round by their osu id
+    pub async fn get_osuvs_division_members(&self, map_id: u32) -> BotResult<HashMap<u32, u8>> {
+        let query = sqlx::query!(
+            "SELECT user_id, division FROM osuvs_division_members WHERE beatmap_id = $1;",
+            map_id as i32
+        );
+        let members = query
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| (row.user_id as u32, row.division as u8))
+            .collect();
+
+        Ok(members)
+    }
+
+    pub async fn get_osuvs_division_member(
+        &self,
+        map_id: u32,
+        user_id: u32,
+    ) -> BotResult<Option<u8>> {
+        let query = sqlx::query!(
+            "SELECT division FROM osuvs_division_members WHERE beatmap_id = $1 AND user_id = $2;",
+            map_id as i32,
+            user_id as i32
+        );
+        let division = query.fetch_optional(&self.pool).await?;
+        Ok(division.map(|row| row.division as u8))
+    }
+
+    /// Players that already have a division keep it
+    pub async fn insert_osuvs_division_members(
+        &self,
+        map_id: u32,
+        members: &[(u32, u8)],
+    ) -> BotResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for &(user_id, division) in members {
+            sqlx::query!(
+                "INSERT INTO osuvs_division_members (beatmap_id, user_id, division) \
+                VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;",
+                map_id as i32,
+                user_id as i32,
+                division as i16
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+fn divisions_from_row(metric: i16, thresholds: Vec<i32>) -> OsuVSDivisions {
+    let thresholds = thresholds.into_iter().map(|t| t as u32).collect();
+
+    OsuVSDivisions::new(OsuVSDivisionMetric::from_i16(metric), thresholds)
+}
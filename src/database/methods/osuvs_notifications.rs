@@ -0,0 +1,37 @@
+use twilight_model::id::UserId;
+
+use crate::{database::Database, error::BotResult};
+
+impl Database {
+    pub async fn insert_osuvs_notification(&self, discord_id: UserId) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "INSERT INTO osuvs_notifications (discord_id) VALUES ($1) ON CONFLICT DO NOTHING;",
+            discord_id.0 as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn remove_osuvs_notification(&self, discord_id: UserId) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "DELETE FROM osuvs_notifications WHERE discord_id = $1;",
+            discord_id.0 as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Discord users linked to the osu account that want to be notified about improvements
+    pub async fn get_osuvs_notification_targets(&self, osu_id: u32) -> BotResult<Vec<UserId>> {
+        let query = sqlx::query!(
+            "SELECT n.discord_id FROM osuvs_notifications n \
+            JOIN manual_links m ON m.discord_id = n.discord_id WHERE m.osu_id = $1;",
+            osu_id as i64
+        );
+        let result = query.fetch_all(&self.pool).await?;
+        Ok(result
+            .into_iter()
+            .map(|entry| UserId(entry.discord_id as u64))
+            .collect())
+    }
+}
@@ -0,0 +1,54 @@
+use futures::StreamExt;
+use rosu_v2::prelude::Beatmap;
+use twilight_model::id::UserId;
+
+use crate::{
+    database::{Database, OsuVSRequest},
+    error::BotResult,
+};
+
+impl Database {
+    pub async fn get_osuvs_requests(&self) -> BotResult<Vec<OsuVSRequest>> {
+        let mut stream =
+            sqlx::query!("SELECT * FROM osuvs_requests ORDER BY requested_at;").fetch(&self.pool);
+        let mut requests = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            let request = OsuVSRequest {
+                map: serde_json::from_value(entry.beatmap)?,
+                requester: UserId(entry.requester as u64),
+                requested_at: entry.requested_at,
+            };
+            requests.push(request);
+        }
+        Ok(requests)
+    }
+
+    pub async fn insert_osuvs_request(&self, map: &Beatmap, requester: UserId) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "INSERT INTO osuvs_requests (beatmap_id, beatmap, requester) VALUES ($1, $2, $3) ON CONFLICT (beatmap_id) DO NOTHING;",
+            map.map_id as i32,
+            serde_json::to_value(map)?,
+            requester.0 as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn osuvs_request_exists(&self, map_id: u32) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "SELECT EXISTS (SELECT 1 FROM osuvs_requests WHERE beatmap_id = $1) AS \"exists!\";",
+            map_id as i32
+        );
+        let result = query.fetch_one(&self.pool).await?;
+        Ok(result.exists)
+    }
+
+    pub async fn remove_osuvs_request(&self, map_id: u32) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "DELETE FROM osuvs_requests WHERE beatmap_id = $1;",
+            map_id as i32
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use rosu_v2::prelude::GameMode;
+
+use crate::{
+    database::{Database, OsuVSMap, OsuVSSeason},
+    error::BotResult,
+};
+
+use super::osuvs::OsuVSMapRow;
+
+struct OsuVSSeasonRow {
+    name: String,
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+    points: Vec<i32>,
+    participation_points: i32,
+}
+
+impl From<OsuVSSeasonRow> for OsuVSSeason {
+    fn from(row: OsuVSSeasonRow) -> Self {
+        Self {
+            name: row.name,
+            start_date: row.start_date,
+            end_date: row.end_date,
+            points: row.points.into_iter().map(|points| points as u32).collect(),
+            participation_points: row.participation_points as u32,
+        }
+    }
+}
+
+impl Database {
+    pub async fn insert_osuvs_season(&self, season: &OsuVSSeason) -> BotResult<bool> {
+        let points: Vec<_> = season.points.iter().map(|&points| points as i32).collect();
+
+        let query = sqlx::query!(
+            "INSERT INTO osuvs_seasons (name, start_date, end_date, points, participation_points) \
+            VALUES ($1, $2, $3, $4, $5) ON CONFLICT (name) DO NOTHING;",
+            season.name,
+            season.start_date,
+            season.end_date,
+            &points,
+            season.participation_points as i32
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Check whether any season overlaps with the given date range
+    pub async fn osuvs_season_overlaps(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "SELECT EXISTS (SELECT 1 FROM osuvs_seasons WHERE start_date < $2 AND end_date > $1) AS \"exists!\";",
+            start_date,
+            end_date
+        );
+        let result = query.fetch_one(&self.pool).await?;
+        Ok(result.exists)
+    }
+
+    pub async fn get_osuvs_season(&self, name: &str) -> BotResult<Option<OsuVSSeason>> {
+        let row = sqlx::query_as!(
+            OsuVSSeasonRow,
+            "SELECT name, start_date, end_date, points, participation_points \
+            FROM osuvs_seasons WHERE LOWER(name) = LOWER($1);",
+            name
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(OsuVSSeason::from))
+    }
+
+    /// The season during the given date
+    pub async fn get_osuvs_season_at(&self, date: DateTime<Utc>) -> BotResult<Option<OsuVSSeason>> {
+        let row = sqlx::query_as!(
+            OsuVSSeasonRow,
+            "SELECT name, start_date, end_date, points, participation_points \
+            FROM osuvs_seasons WHERE start_date <= $1 AND end_date > $1;",
+            date
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(OsuVSSeason::from))
+    }
+
+    /// The most recent season that already started
+    pub async fn get_latest_osuvs_season(&self) -> BotResult<Option<OsuVSSeason>> {
+        let row = sqlx::query_as!(
+            OsuVSSeasonRow,
+            "SELECT name, start_date, end_date, points, participation_points \
+            FROM osuvs_seasons WHERE start_date <= now() ORDER BY start_date DESC LIMIT 1;"
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(OsuVSSeason::from))
+    }
+
+    /// All osuvs maps of the mode that started during the season and ended until the given date
+    pub async fn get_season_osuvs_maps(
+        &self,
+        season: &OsuVSSeason,
+        mode: GameMode,
+        until: DateTime<Utc>,
+    ) -> BotResult<Vec<OsuVSMap>> {
+        let rows = sqlx::query_as!(
+            OsuVSMapRow,
+            "SELECT * FROM osuvs_maps \
+            WHERE start_date >= $1 AND start_date < $2 AND mode = $3 AND end_date <= $4 \
+            ORDER BY start_date;",
+            season.start_date,
+            season.end_date,
+            mode as i16,
+            until
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(OsuVSMap::from).collect())
+    }
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use hashbrown::HashMap;
+
+use crate::{database::Database, error::BotResult};
+
+impl Database {
+    /// Map osu user ids to the last time their recent scores were checked
+    pub async fn get_osuvs_last_checked(&self) -> BotResult<HashMap<u32, DateTime<Utc>>> {
+        let mut stream =
+            sqlx::query!("SELECT user_id, last_checked FROM osuvs_tracking;").fetch(&self.pool);
+        let mut last_checked = HashMap::new();
+
+        while let Some(entry) = stream.next().await.transpose()? {
+            last_checked.insert(entry.user_id as u32, entry.last_checked);
+        }
+
+        Ok(last_checked)
+    }
+
+    pub async fn update_osuvs_last_checked(
+        &self,
+        user_ids: &[u32],
+        last_checked: DateTime<Utc>,
+    ) -> BotResult<()> {
+        let user_ids: Vec<_> = user_ids.iter().map(|&user_id| user_id as i32).collect();
+
+        sqlx::query!(
+            "INSERT INTO osuvs_tracking (user_id, last_checked) SELECT UNNEST($1::INT4[]), $2 \
+            ON CONFLICT (user_id) DO UPDATE SET last_checked = $2;",
+            &user_ids,
+            last_checked
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use futures::StreamExt;
+use twilight_model::id::{RoleId, UserId};
+
+use crate::{
+    database::{Database, PermissionTarget},
+    error::BotResult,
+};
+
+fn permission_target(target_id: i64, is_role: bool) -> PermissionTarget {
+    if is_role {
+        PermissionTarget::Role(RoleId(target_id as u64))
+    } else {
+        PermissionTarget::User(UserId(target_id as u64))
+    }
+}
+
+impl Database {
+    pub async fn get_permission_targets(
+        &self,
+        permission: &str,
+    ) -> BotResult<Vec<PermissionTarget>> {
+        let mut stream = sqlx::query!(
+            "SELECT target_id, is_role FROM permissions WHERE permission = $1;",
+            permission
+        )
+        .fetch(&self.pool);
+        let mut targets = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            targets.push(permission_target(entry.target_id, entry.is_role));
+        }
+        Ok(targets)
+    }
+
+    pub async fn get_permissions(&self) -> BotResult<Vec<(String, PermissionTarget)>> {
+        let mut stream =
+            sqlx::query!("SELECT * FROM permissions ORDER BY permission;").fetch(&self.pool);
+        let mut permissions = Vec::new();
+        while let Some(entry) = stream.next().await.transpose()? {
+            let target = permission_target(entry.target_id, entry.is_role);
+            permissions.push((entry.permission, target));
+        }
+        Ok(permissions)
+    }
+
+    pub async fn insert_permission(
+        &self,
+        permission: &str,
+        target: PermissionTarget,
+    ) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "INSERT INTO permissions (permission, target_id, is_role) VALUES ($1, $2, $3) ON CONFLICT (permission, target_id) DO NOTHING;",
+            permission,
+            target.id() as i64,
+            target.is_role()
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn remove_permission(
+        &self,
+        permission: &str,
+        target: PermissionTarget,
+    ) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "DELETE FROM permissions WHERE permission = $1 AND target_id = $2;",
+            permission,
+            target.id() as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
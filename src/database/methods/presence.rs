@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use twilight_model::id::UserId;
+
+use crate::{database::Database, error::BotResult};
+
+impl Database {
+    /// Returns `false` if the presence was already hidden
+    pub async fn insert_presence_hidden(&self, discord_id: UserId) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "INSERT INTO presence_hidden (discord_id) VALUES ($1) ON CONFLICT DO NOTHING;",
+            discord_id.0 as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn remove_presence_hidden(&self, discord_id: UserId) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "DELETE FROM presence_hidden WHERE discord_id = $1;",
+            discord_id.0 as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Osu ids of linked users that hide their presence
+    pub async fn get_presence_hidden(&self) -> BotResult<HashSet<u32>> {
+        let query = sqlx::query!(
+            "SELECT m.osu_id AS \"osu_id!\" FROM presence_hidden h \
+            JOIN manual_links m ON m.discord_id = h.discord_id;"
+        );
+        let result = query.fetch_all(&self.pool).await?;
+        Ok(result
+            .into_iter()
+            .map(|entry| entry.osu_id as u32)
+            .collect())
+    }
+
+    pub async fn insert_presence_follow(
+        &self,
+        follower: UserId,
+        discord_id: UserId,
+    ) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "INSERT INTO presence_follows (follower, discord_id) VALUES ($1, $2) \
+            ON CONFLICT DO NOTHING;",
+            follower.0 as i64,
+            discord_id.0 as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn remove_presence_follow(
+        &self,
+        follower: UserId,
+        discord_id: UserId,
+    ) -> BotResult<bool> {
+        let query = sqlx::query!(
+            "DELETE FROM presence_follows WHERE follower = $1 AND discord_id = $2;",
+            follower.0 as i64,
+            discord_id.0 as i64
+        );
+        let result = query.execute(&self.pool).await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Discord users that want to be notified when the osu account comes online
+    pub async fn get_presence_followers(&self, osu_id: u32) -> BotResult<Vec<UserId>> {
+        let query = sqlx::query!(
+            "SELECT f.follower FROM presence_follows f \
+            JOIN manual_links m ON m.discord_id = f.discord_id WHERE m.osu_id = $1;",
+            osu_id as i64
+        );
+        let result = query.fetch_all(&self.pool).await?;
+        Ok(result
+            .into_iter()
+            .map(|entry| UserId(entry.follower as u64))
+            .collect())
+    }
+}
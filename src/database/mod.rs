@@ -5,6 +5,12 @@ use sqlx::{postgres::PgPoolOptions, PgPool};
 
 use crate::error::BotResult;
 
+pub use models::{
+    HallOfFameEntry, OsuVSAnnouncement, OsuVSDivisionMetric, OsuVSDivisions, OsuVSMap,
+    OsuVSMapBounds, OsuVSMetric, OsuVSPlacement, OsuVSRequest, OsuVSRules, OsuVSSeason,
+    PermissionTarget, DEFAULT_PARTICIPATION_POINTS, DEFAULT_SEASON_POINTS,
+};
+
 pub struct Database {
     pool: PgPool,
 }
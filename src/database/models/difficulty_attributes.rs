@@ -0,0 +1,60 @@
+use rosu_pp::{
+    fruits::FruitsDifficultyAttributes, mania::ManiaDifficultyAttributes,
+    osu::OsuDifficultyAttributes, taiko::TaikoDifficultyAttributes, DifficultyAttributes,
+};
+use serde_derive::{Deserialize, Serialize};
+
+/// Wrapper to store difficulty attributes of rosu-pp as json
+#[derive(Deserialize, Serialize)]
+pub struct StoredDifficultyAttributes(
+    #[serde(with = "DifficultyAttributesDef")] pub DifficultyAttributes,
+);
+
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "DifficultyAttributes")]
+enum DifficultyAttributesDef {
+    Fruits(#[serde(with = "FruitsDifficultyAttributesDef")] FruitsDifficultyAttributes),
+    Mania(#[serde(with = "ManiaDifficultyAttributesDef")] ManiaDifficultyAttributes),
+    Osu(#[serde(with = "OsuDifficultyAttributesDef")] OsuDifficultyAttributes),
+    Taiko(#[serde(with = "TaikoDifficultyAttributesDef")] TaikoDifficultyAttributes),
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "FruitsDifficultyAttributes")]
+struct FruitsDifficultyAttributesDef {
+    stars: f64,
+    ar: f64,
+    n_fruits: usize,
+    n_droplets: usize,
+    n_tiny_droplets: usize,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "ManiaDifficultyAttributes")]
+struct ManiaDifficultyAttributesDef {
+    stars: f64,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "OsuDifficultyAttributes")]
+struct OsuDifficultyAttributesDef {
+    aim_strain: f64,
+    speed_strain: f64,
+    flashlight_rating: f64,
+    slider_factor: f64,
+    ar: f64,
+    od: f64,
+    hp: f64,
+    n_circles: usize,
+    n_sliders: usize,
+    n_spinners: usize,
+    stars: f64,
+    max_combo: usize,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "TaikoDifficultyAttributes")]
+struct TaikoDifficultyAttributesDef {
+    stars: f64,
+    max_combo: usize,
+}
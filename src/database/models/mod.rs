@@ -0,0 +1,21 @@
+mod difficulty_attributes;
+mod osuvs_announcement;
+mod osuvs_bounds;
+mod osuvs_divisions;
+mod osuvs_map;
+mod osuvs_placement;
+mod osuvs_request;
+mod osuvs_rules;
+mod osuvs_season;
+mod permission;
+
+pub use difficulty_attributes::StoredDifficultyAttributes;
+pub use osuvs_announcement::OsuVSAnnouncement;
+pub use osuvs_bounds::OsuVSMapBounds;
+pub use osuvs_divisions::{OsuVSDivisionMetric, OsuVSDivisions};
+pub use osuvs_map::OsuVSMap;
+pub use osuvs_placement::{HallOfFameEntry, OsuVSPlacement};
+pub use osuvs_request::OsuVSRequest;
+pub use osuvs_rules::{OsuVSMetric, OsuVSRules};
+pub use osuvs_season::{OsuVSSeason, DEFAULT_PARTICIPATION_POINTS, DEFAULT_SEASON_POINTS};
+pub use permission::PermissionTarget;
@@ -0,0 +1,6 @@
+/// Messages that are posted exactly once per osuvs round
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OsuVSAnnouncement {
+    Start = 0,
+    End = 1,
+}
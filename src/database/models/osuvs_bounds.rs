@@ -0,0 +1,86 @@
+use std::fmt;
+
+use crate::utils::{datetime::sec_to_minsec, numbers::round};
+
+/// Limits set by admins that maps of an osuvs must stay within
+#[derive(Copy, Clone, Debug, Default)]
+pub struct OsuVSMapBounds {
+    pub min_stars: Option<f32>,
+    pub max_stars: Option<f32>,
+    /// Drain length in seconds
+    pub min_length: Option<u32>,
+    /// Drain length in seconds
+    pub max_length: Option<u32>,
+}
+
+impl OsuVSMapBounds {
+    pub fn is_empty(&self) -> bool {
+        self.min_stars.is_none()
+            && self.max_stars.is_none()
+            && self.min_length.is_none()
+            && self.max_length.is_none()
+    }
+
+    /// Describe the first bound that the map violates
+    pub fn check(&self, stars: f32, length: u32) -> Result<(), String> {
+        if let Some(min) = self.min_stars.filter(|&min| stars < min) {
+            return Err(format!(
+                "The map has {}★ but must have at least {}★!",
+                round(stars),
+                round(min)
+            ));
+        }
+
+        if let Some(max) = self.max_stars.filter(|&max| stars > max) {
+            return Err(format!(
+                "The map has {}★ but must have at most {}★!",
+                round(stars),
+                round(max)
+            ));
+        }
+
+        if let Some(min) = self.min_length.filter(|&min| length < min) {
+            return Err(format!(
+                "The map has a drain length of {} but it must be at least {}!",
+                sec_to_minsec(length),
+                sec_to_minsec(min)
+            ));
+        }
+
+        if let Some(max) = self.max_length.filter(|&max| length > max) {
+            return Err(format!(
+                "The map has a drain length of {} but it must be at most {}!",
+                sec_to_minsec(length),
+                sec_to_minsec(max)
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for OsuVSMapBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("No limits");
+        }
+
+        f.write_str("Stars: ")?;
+
+        match (self.min_stars, self.max_stars) {
+            (Some(min), Some(max)) => write!(f, "{}★ - {}★", round(min), round(max))?,
+            (Some(min), None) => write!(f, "at least {}★", round(min))?,
+            (None, Some(max)) => write!(f, "at most {}★", round(max))?,
+            (None, None) => f.write_str("any")?,
+        }
+
+        f.write_str(" | Drain length: ")?;
+
+        match (self.min_length, self.max_length) {
+            (Some(min), Some(max)) => write!(f, "{} - {}", sec_to_minsec(min), sec_to_minsec(max)),
+            (Some(min), None) => write!(f, "at least {}", sec_to_minsec(min)),
+            (None, Some(max)) => write!(f, "at most {}", sec_to_minsec(max)),
+            (None, None) => f.write_str("any"),
+        }
+    }
+}
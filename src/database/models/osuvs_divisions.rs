@@ -0,0 +1,120 @@
+use std::fmt;
+
+use crate::utils::numbers::with_comma_uint;
+
+/// Statistic of a player by which they're put into a division
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OsuVSDivisionMetric {
+    Rank = 0,
+    Pp = 1,
+}
+
+impl OsuVSDivisionMetric {
+    pub fn from_i16(metric: i16) -> Self {
+        match metric {
+            1 => Self::Pp,
+            _ => Self::Rank,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rank" => Some(Self::Rank),
+            "pp" => Some(Self::Pp),
+            _ => None,
+        }
+    }
+}
+
+/// Thresholds that split the participants of an osuvs into divisions.
+///
+/// Division 1 contains the best players. Rank thresholds are the worst rank
+/// and pp thresholds the least pp that still belong to a division.
+#[derive(Clone, Debug)]
+pub struct OsuVSDivisions {
+    pub metric: OsuVSDivisionMetric,
+    pub thresholds: Vec<u32>,
+}
+
+impl OsuVSDivisions {
+    pub fn new(metric: OsuVSDivisionMetric, mut thresholds: Vec<u32>) -> Self {
+        thresholds.sort_unstable();
+        thresholds.dedup();
+
+        if metric == OsuVSDivisionMetric::Pp {
+            thresholds.reverse();
+        }
+
+        Self { metric, thresholds }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.thresholds.is_empty()
+    }
+
+    /// Amount of divisions
+    pub fn count(&self) -> u8 {
+        self.thresholds.len() as u8 + 1
+    }
+
+    /// The division of a player with the given global rank and pp.
+    /// Unranked players are put into the last division.
+    pub fn division(&self, rank: Option<u32>, pp: f32) -> u8 {
+        let missed = match (self.metric, rank) {
+            (OsuVSDivisionMetric::Rank, Some(rank)) => {
+                self.thresholds.iter().filter(|&&max| rank > max).count()
+            }
+            (OsuVSDivisionMetric::Rank, None) => self.thresholds.len(),
+            (OsuVSDivisionMetric::Pp, _) => self
+                .thresholds
+                .iter()
+                .filter(|&&min| pp < min as f32)
+                .count(),
+        };
+
+        missed as u8 + 1
+    }
+
+    /// Name of the division including its range e.g. `Division 2 (#1,001 - #10,000)`
+    pub fn name(&self, division: u8) -> String {
+        let idx = division as usize - 1;
+        let upper = idx.checked_sub(1).and_then(|idx| self.thresholds.get(idx));
+        let lower = self.thresholds.get(idx);
+
+        let range = match (self.metric, upper, lower) {
+            (_, None, None) => return format!("Division {}", division),
+            (OsuVSDivisionMetric::Rank, None, Some(max)) => {
+                format!("top #{}", with_comma_uint(*max))
+            }
+            (OsuVSDivisionMetric::Rank, Some(min), Some(max)) => {
+                format!("#{} - #{}", with_comma_uint(min + 1), with_comma_uint(*max))
+            }
+            (OsuVSDivisionMetric::Rank, Some(min), None) => {
+                format!("#{}+", with_comma_uint(min + 1))
+            }
+            (OsuVSDivisionMetric::Pp, None, Some(min)) => format!("{}pp+", with_comma_uint(*min)),
+            (OsuVSDivisionMetric::Pp, Some(max), Some(min)) => {
+                format!("{} - {}pp", with_comma_uint(*min), with_comma_uint(max - 1))
+            }
+            (OsuVSDivisionMetric::Pp, Some(max), None) => {
+                format!("below {}pp", with_comma_uint(*max))
+            }
+        };
+
+        format!("Division {} ({})", division, range)
+    }
+}
+
+impl fmt::Display for OsuVSDivisions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("No divisions, everyone competes on one leaderboard");
+        }
+
+        for division in 1..=self.count() {
+            writeln!(f, "• {}", self.name(division))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use rosu_v2::prelude::{GameMode, Grade, Score};
+
+use super::OsuVSRules;
+
+#[derive(Copy, Clone, Debug)]
+pub struct OsuVSMap {
+    pub map_id: u32,
+    pub mode: GameMode,
+    pub rules: OsuVSRules,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+}
+
+impl OsuVSMap {
+    /// Whether the score counts for this osuvs
+    pub fn accepts(&self, score: &Score) -> bool {
+        score.map.as_ref().map(|map| map.map_id) == Some(self.map_id)
+            && score.grade(None) != Grade::F
+            && score.created_at >= self.start_date
+            && score.created_at < self.end_date
+            && self.rules.allows(score.mods)
+    }
+}
@@ -0,0 +1,22 @@
+use rosu_v2::prelude::GameMods;
+
+/// Placement of a player in a finished osuvs round
+#[derive(Clone, Debug)]
+pub struct OsuVSPlacement {
+    pub map_id: u32,
+    /// Zero-based placement within the round
+    pub placement: usize,
+    /// Name of the player at the time of the score, if known
+    pub username: Option<String>,
+    pub mods: GameMods,
+}
+
+/// Achievements of a player across all finished rounds of a mode
+#[derive(Clone, Debug)]
+pub struct HallOfFameEntry {
+    pub user_id: u32,
+    pub username: String,
+    pub wins: usize,
+    pub podiums: usize,
+    pub rounds: usize,
+}
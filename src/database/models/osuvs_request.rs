@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+use rosu_v2::prelude::Beatmap;
+use twilight_model::id::UserId;
+
+pub struct OsuVSRequest {
+    pub map: Beatmap,
+    pub requester: UserId,
+    pub requested_at: DateTime<Utc>,
+}
@@ -0,0 +1,231 @@
+use std::{cmp::Ordering, fmt};
+
+use rosu_v2::prelude::{GameMods, Score};
+
+/// Value by which the scores of an osuvs are ranked
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OsuVSMetric {
+    Score = 0,
+    Accuracy = 1,
+    Pp = 2,
+    Combo = 3,
+    Misses = 4,
+}
+
+impl OsuVSMetric {
+    pub const ALL: [Self; 5] = [
+        Self::Score,
+        Self::Accuracy,
+        Self::Pp,
+        Self::Combo,
+        Self::Misses,
+    ];
+
+    pub fn from_i16(metric: i16) -> Self {
+        match metric {
+            1 => Self::Accuracy,
+            2 => Self::Pp,
+            3 => Self::Combo,
+            4 => Self::Misses,
+            _ => Self::Score,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|metric| metric.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Score => "score",
+            Self::Accuracy => "accuracy",
+            Self::Pp => "pp",
+            Self::Combo => "combo",
+            Self::Misses => "misses",
+        }
+    }
+
+    /// Compare two scores such that the better score comes first.
+    /// The pp metric requires the pp of both scores to be calculated beforehand.
+    pub fn cmp(self, a: &Score, b: &Score) -> Ordering {
+        match self {
+            Self::Score => b.score.cmp(&a.score),
+            Self::Accuracy => b
+                .accuracy
+                .partial_cmp(&a.accuracy)
+                .unwrap_or(Ordering::Equal),
+            Self::Pp => {
+                let a = a.pp.unwrap_or(0.0);
+                let b = b.pp.unwrap_or(0.0);
+
+                b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+            }
+            Self::Combo => b.max_combo.cmp(&a.max_combo),
+            Self::Misses => a.statistics.count_miss.cmp(&b.statistics.count_miss),
+        }
+    }
+}
+
+impl fmt::Display for OsuVSMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Rules which scores of an osuvs have to follow and how they are ranked
+#[derive(Copy, Clone, Debug)]
+pub struct OsuVSRules {
+    pub required_mods: GameMods,
+    pub forbidden_mods: GameMods,
+    pub metric: OsuVSMetric,
+    /// Decides between scores with the same metric value.
+    /// Remaining ties go to the score that was set first.
+    pub tiebreak: Option<OsuVSMetric>,
+}
+
+impl OsuVSRules {
+    pub fn allows(&self, mods: GameMods) -> bool {
+        mods.contains(self.required_mods) && !mods.intersects(self.forbidden_mods)
+    }
+
+    pub fn uses_pp(&self) -> bool {
+        self.metric == OsuVSMetric::Pp || self.tiebreak == Some(OsuVSMetric::Pp)
+    }
+
+    /// Compare two scores such that the better score comes first
+    pub fn cmp(&self, a: &Score, b: &Score) -> Ordering {
+        self.metric
+            .cmp(a, b)
+            .then_with(|| match self.tiebreak {
+                Some(tiebreak) => tiebreak.cmp(a, b),
+                None => Ordering::Equal,
+            })
+            .then_with(|| a.created_at.cmp(&b.created_at))
+    }
+}
+
+impl Default for OsuVSRules {
+    fn default() -> Self {
+        Self {
+            required_mods: GameMods::NoMod,
+            forbidden_mods: GameMods::ScoreV2,
+            metric: OsuVSMetric::Score,
+            tiebreak: None,
+        }
+    }
+}
+
+impl fmt::Display for OsuVSRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.forbidden_mods.is_all() {
+            f.write_str("Mods: `NM` only")?;
+        } else {
+            f.write_str("Mods: ")?;
+
+            if self.required_mods.is_empty() {
+                f.write_str("any")?;
+            } else {
+                write!(f, "`{}` required", self.required_mods)?;
+            }
+
+            if !self.forbidden_mods.is_empty() {
+                write!(f, ", `{}` forbidden", self.forbidden_mods)?;
+            }
+        }
+
+        write!(f, " | Ranked by: {}", self.metric)?;
+
+        if let Some(tiebreak) = self.tiebreak {
+            write!(f, " | Tiebreak: {}", tiebreak)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{TimeZone, Utc};
+    use rosu_v2::prelude::{GameMode, Grade, ScoreStatistics};
+
+    fn score(score: u32, accuracy: f32, misses: u32, secs: i64) -> Score {
+        Score {
+            accuracy,
+            created_at: Utc.timestamp(secs, 0),
+            grade: Grade::A,
+            max_combo: 100,
+            map: None,
+            mapset: None,
+            mode: GameMode::STD,
+            mods: GameMods::NoMod,
+            perfect: false,
+            pp: None,
+            rank_country: None,
+            rank_global: None,
+            replay: false,
+            score,
+            score_id: 0,
+            statistics: ScoreStatistics {
+                count_geki: 0,
+                count_300: 0,
+                count_katu: 0,
+                count_100: 0,
+                count_50: 0,
+                count_miss: misses,
+            },
+            user: None,
+            user_id: 0,
+            weight: None,
+        }
+    }
+
+    #[test]
+    fn test_allows() {
+        let rules = OsuVSRules {
+            required_mods: GameMods::Hidden,
+            forbidden_mods: GameMods::DoubleTime | GameMods::ScoreV2,
+            ..Default::default()
+        };
+
+        assert!(rules.allows(GameMods::Hidden));
+        assert!(rules.allows(GameMods::Hidden | GameMods::HardRock));
+        assert!(!rules.allows(GameMods::NoMod));
+        assert!(!rules.allows(GameMods::Hidden | GameMods::DoubleTime));
+        assert!(!rules.allows(GameMods::Hidden | GameMods::ScoreV2));
+        assert!(!OsuVSRules::default().allows(GameMods::ScoreV2));
+    }
+
+    #[test]
+    fn test_cmp() {
+        let rules = OsuVSRules {
+            metric: OsuVSMetric::Misses,
+            tiebreak: Some(OsuVSMetric::Accuracy),
+            ..Default::default()
+        };
+
+        let best = score(1000, 99.0, 0, 2);
+        let tied = score(2000, 99.0, 0, 3);
+        let worse_acc = score(3000, 95.0, 0, 1);
+        let most_misses = score(4000, 100.0, 5, 0);
+
+        let mut scores = [
+            most_misses.clone(),
+            tied.clone(),
+            worse_acc.clone(),
+            best.clone(),
+        ];
+        scores.sort_unstable_by(|a, b| rules.cmp(a, b));
+        let order: Vec<_> = scores.iter().map(|score| score.score).collect();
+
+        assert_eq!(
+            order,
+            vec![best.score, tied.score, worse_acc.score, most_misses.score]
+        );
+        assert_eq!(OsuVSRules::default().cmp(&best, &tied), Ordering::Greater);
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+
+pub const DEFAULT_SEASON_POINTS: [u32; 10] = [25, 18, 15, 12, 10, 8, 6, 4, 2, 1];
+pub const DEFAULT_PARTICIPATION_POINTS: u32 = 1;
+
+#[derive(Clone, Debug)]
+pub struct OsuVSSeason {
+    pub name: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    /// Points for the first place, second place, ...
+    pub points: Vec<u32>,
+    /// Points for everyone that submitted a score
+    pub participation_points: u32,
+}
+
+impl OsuVSSeason {
+    /// Points for the zero-based placement in a round
+    pub fn placement_points(&self, placement: usize) -> u32 {
+        self.points.get(placement).copied().unwrap_or(0) + self.participation_points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placement_points() {
+        let season = OsuVSSeason {
+            name: "Test".to_owned(),
+            start_date: Utc::now(),
+            end_date: Utc::now(),
+            points: vec![10, 5],
+            participation_points: 1,
+        };
+
+        assert_eq!(season.placement_points(0), 11);
+        assert_eq!(season.placement_points(1), 6);
+        assert_eq!(season.placement_points(2), 1);
+    }
+}
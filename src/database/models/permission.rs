@@ -0,0 +1,31 @@
+use std::fmt;
+
+use twilight_model::id::{RoleId, UserId};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PermissionTarget {
+    Role(RoleId),
+    User(UserId),
+}
+
+impl PermissionTarget {
+    pub fn id(self) -> u64 {
+        match self {
+            Self::Role(role_id) => role_id.0,
+            Self::User(user_id) => user_id.0,
+        }
+    }
+
+    pub fn is_role(self) -> bool {
+        matches!(self, Self::Role(_))
+    }
+}
+
+impl fmt::Display for PermissionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Role(role_id) => write!(f, "<@&{}>", role_id),
+            Self::User(user_id) => write!(f, "<@{}>", user_id),
+        }
+    }
+}
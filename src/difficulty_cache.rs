@@ -0,0 +1,147 @@
+use std::{collections::HashMap, sync::Arc, time::Instant};
+
+use parking_lot::Mutex;
+use rosu_pp::{Beatmap as PpBeatmap, DifficultyAttributes};
+use rosu_v2::prelude::{GameMode, GameMods};
+
+use crate::{database::Database, error::BotResult};
+
+/// How many parsed maps are kept in memory
+const MAX_PARSED_MAPS: usize = 32;
+
+struct ParsedMap {
+    checksum: String,
+    map: Arc<PpBeatmap>,
+    last_used: Instant,
+}
+
+/// Difficulty attributes keyed by map id, mods and mode.
+///
+/// Attributes are held in memory and persisted in the database. They belong
+/// to the checksum of the `.osu` file they were calculated on so that they
+/// are dropped once the file of a map changes.
+///
+/// The most recently used maps are also kept parsed so that their
+/// `.osu` file doesn't have to be read and parsed on every calculation.
+#[derive(Default)]
+pub struct DifficultyCache {
+    checksums: Mutex<HashMap<u32, String>>,
+    attributes: Mutex<HashMap<(u32, GameMods, GameMode), DifficultyAttributes>>,
+    maps: Mutex<HashMap<u32, ParsedMap>>,
+}
+
+impl DifficultyCache {
+    /// The parsed map if it belongs to the checksum
+    pub fn map(&self, map_id: u32, checksum: &str) -> Option<Arc<PpBeatmap>> {
+        let mut maps = self.maps.lock();
+        let parsed = maps
+            .get_mut(&map_id)
+            .filter(|parsed| parsed.checksum == checksum)?;
+        parsed.last_used = Instant::now();
+
+        Some(Arc::clone(&parsed.map))
+    }
+
+    /// Keep the parsed map, replacing the least recently used one if necessary
+    pub fn insert_map(&self, map_id: u32, checksum: &str, map: Arc<PpBeatmap>) {
+        let mut maps = self.maps.lock();
+
+        if maps.len() >= MAX_PARSED_MAPS && !maps.contains_key(&map_id) {
+            let oldest = maps
+                .iter()
+                .min_by_key(|(_, parsed)| parsed.last_used)
+                .map(|(&map_id, _)| map_id);
+
+            if let Some(oldest) = oldest {
+                maps.remove(&oldest);
+            }
+        }
+
+        let parsed = ParsedMap {
+            checksum: checksum.to_owned(),
+            map,
+            last_used: Instant::now(),
+        };
+
+        maps.insert(map_id, parsed);
+    }
+
+    /// Drop all attributes of the map that don't belong to the checksum
+    pub async fn validate(
+        &self,
+        database: &Database,
+        map_id: u32,
+        checksum: &str,
+    ) -> BotResult<()> {
+        let unchanged =
+            matches!(self.checksums.lock().get(&map_id), Some(cached) if cached == checksum);
+
+        if unchanged {
+            return Ok(());
+        }
+
+        database
+            .remove_stale_difficulty_attributes(map_id, checksum)
+            .await?;
+
+        self.attributes.lock().retain(|(id, ..), _| *id != map_id);
+        self.checksums.lock().insert(map_id, checksum.to_owned());
+
+        Ok(())
+    }
+
+    /// Get the attributes from memory, the database, or calculate them
+    pub async fn get(
+        &self,
+        database: &Database,
+        map: &PpBeatmap,
+        map_id: u32,
+        checksum: &str,
+        mods: GameMods,
+        mode: GameMode,
+    ) -> BotResult<DifficultyAttributes> {
+        let key = (map_id, mods, mode);
+
+        if let Some(attributes) = self.attributes.lock().get(&key) {
+            return Ok(attributes.clone());
+        }
+
+        let stored = database
+            .get_difficulty_attributes(map_id, mods, mode, checksum)
+            .await?;
+
+        let attributes = match stored {
+            Some(attributes) => attributes,
+            None => {
+                let attributes = calculate_difficulty(map, mods, mode);
+
+                database
+                    .insert_difficulty_attributes(map_id, mods, mode, checksum, attributes.clone())
+                    .await?;
+
+                attributes
+            }
+        };
+
+        self.attributes.lock().insert(key, attributes.clone());
+
+        Ok(attributes)
+    }
+}
+
+/// The calculation is chosen based on the mode instead of the map
+/// so that converted maps are handled as well.
+pub fn calculate_difficulty(
+    map: &PpBeatmap,
+    mods: GameMods,
+    mode: GameMode,
+) -> DifficultyAttributes {
+    let mods = mods.bits();
+
+    match mode {
+        GameMode::STD => DifficultyAttributes::Osu(rosu_pp::osu::stars(map, mods, None)),
+        GameMode::TKO => DifficultyAttributes::Taiko(rosu_pp::taiko::stars(map, mods, None)),
+        GameMode::CTB => DifficultyAttributes::Fruits(rosu_pp::fruits::stars(map, mods, None)),
+        GameMode::MNA => DifficultyAttributes::Mania(rosu_pp::mania::stars(map, mods, None)),
+    }
+}
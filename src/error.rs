@@ -66,10 +66,20 @@ pub enum Error {
 
 #[derive(Debug, thiserror::Error)]
 pub enum MapDownloadError {
-    #[error("Reqwest error.")]
-    Reqwest(#[from] ReqwestError),
+    #[error("Checksum of map id {map_id} is {actual} instead of {expected}.")]
+    ChecksumMismatch {
+        map_id: u32,
+        expected: String,
+        actual: String,
+    },
     #[error("I/O error.")]
     Io(#[from] IoError),
+    #[error("Missing environment variable (BEATMAPS_FOLDER).")]
+    MissingFolder,
+    #[error("Failed to retrieve the checksum from the osu! API.")]
+    Osu(#[from] OsuError),
+    #[error("Reqwest error.")]
+    Reqwest(#[from] ReqwestError),
     #[error("Failed to download map id {0}.")]
     RetryLimit(u32),
 }
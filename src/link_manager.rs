@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use futures::future::join_all;
+use parking_lot::Mutex;
+use rosu_v2::{
+    prelude::{OsuError, User},
+    Osu as OsuClient,
+};
+
+use crate::{
+    database::Database, error::BotResult, osu_irc::IrcClient, utils::osu::username_to_number,
+};
+
+/// Keeps the IRC targets in sync with the linked osu! users.
+///
+/// Targets are keyed by osu! id. Since IRC only knows usernames, the
+/// current username of every tracked user is kept so that the name index
+/// of the IRC client can be updated when they rename.
+#[derive(Default)]
+pub struct LinkManager {
+    usernames: Mutex<HashMap<u32, String>>,
+}
+
+impl LinkManager {
+    /// Track all linked users, their usernames are requested concurrently
+    pub async fn new(osu: &OsuClient, database: &Database, irc: &IrcClient) -> BotResult<Self> {
+        let mut osu_ids: Vec<_> = database
+            .get_manual_links()
+            .await?
+            .into_iter()
+            .map(|(_, osu_id)| osu_id)
+            .collect();
+
+        osu_ids.sort_unstable();
+        osu_ids.dedup();
+
+        let manager = Self::default();
+        manager.update(osu, irc, osu_ids).await;
+        info!(
+            "Tracking {} linked users on IRC",
+            manager.usernames.lock().len()
+        );
+
+        Ok(manager)
+    }
+
+    pub fn is_tracked(&self, osu_id: u32) -> bool {
+        self.usernames.lock().contains_key(&osu_id)
+    }
+
+    /// Current username of the tracked osu! user
+    pub fn username(&self, osu_id: u32) -> Option<String> {
+        self.usernames.lock().get(&osu_id).cloned()
+    }
+
+    /// Start tracking the osu! user on IRC
+    pub async fn track(&self, osu: &OsuClient, irc: &IrcClient, osu_id: u32) -> BotResult<()> {
+        if !self.is_tracked(osu_id) {
+            let user = osu.user(osu_id).await?;
+            self.insert(irc, &user);
+        }
+
+        Ok(())
+    }
+
+    /// Stop tracking the osu! user on IRC
+    pub fn untrack(&self, irc: &IrcClient, osu_id: u32) {
+        if let Some(username) = self.usernames.lock().remove(&osu_id) {
+            remove_name(irc, &username, osu_id);
+            irc.targets.write(osu_id).remove();
+        }
+    }
+
+    /// Request all tracked users again to pick up renamed users
+    pub async fn refresh(&self, osu: &OsuClient, irc: &IrcClient) {
+        let osu_ids: Vec<_> = self.usernames.lock().keys().copied().collect();
+        self.update(osu, irc, osu_ids).await;
+    }
+
+    async fn update(&self, osu: &OsuClient, irc: &IrcClient, osu_ids: Vec<u32>) {
+        let user_reqs = osu_ids.iter().map(|&osu_id| osu.user(osu_id));
+        let users = join_all(user_reqs).await;
+
+        for (osu_id, user) in osu_ids.into_iter().zip(users) {
+            match user {
+                Ok(user) => self.insert(irc, &user),
+                Err(OsuError::NotFound) => warn!("User with osu_id {} was not found", osu_id),
+                Err(why) => unwind_error!(
+                    error,
+                    why,
+                    "Error when retrieving username of linked user: {}"
+                ),
+            }
+        }
+    }
+
+    fn insert(&self, irc: &IrcClient, user: &User) {
+        let osu_id = user.user_id;
+        let prev = self.usernames.lock().insert(osu_id, user.username.clone());
+
+        // Old names must no longer resolve to the user, the current one is added below
+        if let Some(prev) = prev.filter(|prev| *prev != user.username) {
+            info!("Linked user {} renamed to {}", prev, user.username);
+            remove_name(irc, &prev, osu_id);
+        }
+
+        for prev in user.previous_usernames.iter().flatten() {
+            remove_name(irc, prev, osu_id);
+        }
+
+        let mut name = irc.names.write(username_to_number(&user.username));
+
+        // The previous owner of the name must have renamed
+        if let Some(prev_id) = name.get().copied().filter(|&prev_id| prev_id != osu_id) {
+            info!(
+                "Name {} moved from user {} to user {}",
+                user.username, prev_id, osu_id
+            );
+        }
+
+        name.insert(osu_id);
+
+        // Keep the online state if the user is already tracked
+        let mut target = irc.targets.write(osu_id);
+
+        if target.get().is_none() {
+            target.insert(None);
+        }
+    }
+}
+
+/// Remove the name from the IRC name index if it still belongs to the user
+fn remove_name(irc: &IrcClient, username: &str, osu_id: u32) {
+    let mut name = irc.names.write(username_to_number(username));
+
+    if name.get() == Some(&osu_id) {
+        name.remove();
+    }
+}
@@ -1,5 +1,7 @@
 mod background_loop;
 mod osuvs;
+mod presence;
 
 pub use background_loop::background_loop;
 pub use osuvs::*;
+pub use presence::presence_feed;
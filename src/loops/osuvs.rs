@@ -1,139 +1,365 @@
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use futures::{
-    future::{try_join_all, FutureExt},
+    future::FutureExt,
     stream::{FuturesUnordered, StreamExt},
 };
-use rosu_pp::{osu::OsuPerformanceAttributes, Beatmap, OsuPP};
-use rosu_v2::prelude::{GameMode, GameMods, Grade, Score};
-use std::fmt::Write;
-use std::{collections::HashMap, sync::Arc};
+use rosu_v2::prelude::{GameMode, Score};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    sync::{atomic::Ordering as AtomicOrdering, Arc},
+};
 use tokio::time::{interval, Duration};
+use twilight_model::guild::Member;
 
 use crate::{
     context::Context,
+    database::{OsuVSAnnouncement, OsuVSMap},
     error::BotResult,
     utils::{
         datetime::sec_to_minsec,
-        numbers::{round, with_comma_uint},
-        osu::{map_to_string, prepare_beatmap_file},
-        Author, EmbedBuilder, Footer, EMOTE_MEDALS, EMOTE_RANKS, OSUVS_CHANNEL, OSUVS_DATE_FORMAT,
-        OSU_BASE,
+        numbers::round,
+        osu::{map_to_string, mode_name},
+        osuvs::{
+            calculate_pp, notify_improvement, osuvs_leaderboard, score_username, season_standings,
+            snapshot_divisions, standings_embed, submit_score, write_entry,
+        },
+        pagination::Pages,
+        Author, EmbedBuilder, Footer, APPROVE_CHANNEL, EMOTE_MEDALS, OSUVS_CHANNEL,
+        OSUVS_DATE_FORMAT, OSU_BASE, SERVER_ID,
     },
 };
 
-const OSUVS_TRACK_INTERVAL: u64 = 300;
+/// How often the tracking loop runs
+const OSUVS_TRACK_INTERVAL: u64 = 60;
+/// Minimum amount of seconds between two checks of an online user
+const ONLINE_CHECK_INTERVAL: i64 = 120;
+/// Minimum amount of seconds between two checks of an offline user
+const OFFLINE_CHECK_INTERVAL: i64 = 1800;
+/// How many iterations pass until usernames are refreshed to notice renames
+const USERNAME_REFRESH_ITERATIONS: usize = 60;
+/// Maximum amount of embeds in a single message
+const EMBEDS_PER_MESSAGE: usize = 10;
 
 pub async fn osu_tracking(ctx: Arc<Context>) {
     let mut interval = interval(Duration::from_secs(OSUVS_TRACK_INTERVAL));
     interval.tick().await;
 
-    let mut user_ids = HashMap::new();
+    // Announcements that were claimed but never sent are retried
+    match ctx.database.release_pending_osuvs_announcements().await {
+        Ok(0) => {}
+        Ok(released) => info!("Retrying {} unsent osuvs announcements", released),
+        Err(why) => unwind_error!(error, why, "Error while releasing osuvs announcements: {}"),
+    }
 
-    loop {
+    // Catch up on announcements that were missed while the bot was offline
+    announce_all(&ctx, OsuVSAnnouncement::Start, Utc::now()).await;
+    announce_all(&ctx, OsuVSAnnouncement::End, Utc::now()).await;
+
+    let mut irc_connections = ctx.irc.connections.load(AtomicOrdering::Acquire);
+
+    for iteration in 1.. {
         interval.tick().await;
 
-        let (map_id, start, end) = match ctx.database.get_curr_osuvs_map().await {
-            Some(tuple) => tuple,
-            None => continue,
-        };
+        if iteration % USERNAME_REFRESH_ITERATIONS == 0 {
+            ctx.links.refresh(&ctx.osu, &ctx.irc).await;
+        }
 
         let now = Utc::now();
-        if now - start < ChronoDuration::seconds(OSUVS_TRACK_INTERVAL as i64) {
-            if let Err(why) = map_start(&ctx, map_id, end).await {
-                unwind_error!(error, why, "Error while announcing end of osuvs map: {}");
-            }
+        announce_all(&ctx, OsuVSAnnouncement::Start, now).await;
+
+        // After IRC reconnected, everyone is checked so that
+        // no score from the time without online states is missed
+        let connections = ctx.irc.connections.load(AtomicOrdering::Acquire);
+        let backfill = connections != irc_connections;
+        irc_connections = connections;
+
+        let osuvs_maps = ctx.database.get_curr_osuvs_maps().await;
+
+        if !osuvs_maps.is_empty() {
+            track_scores(&ctx, &osuvs_maps, now, backfill).await;
         }
 
-        let mut users = Vec::new();
-
-        for (number, online) in ctx.irc.targets.iter() {
-            if !user_ids.contains_key(number) {
-                let bytes = number.to_be_bytes();
-                let mut slice = &bytes[..];
-                while let [first, rest @ ..] = slice {
-                    if *first == 0 {
-                        slice = rest;
-                    } else {
-                        break;
-                    }
-                }
-                let name = std::str::from_utf8(slice).unwrap();
-                match ctx.osu.user(name).await {
-                    Ok(user) => {
-                        user_ids.insert(number, user.user_id);
-                    }
-                    Err(why) => {
-                        unwind_error!(warn, why, "Failed to add user id`: {}");
-                        continue;
-                    }
-                }
-            }
+        // Rounds ending before the next iteration get their results posted now
+        let until = now + ChronoDuration::seconds(OSUVS_TRACK_INTERVAL as i64);
+        announce_all(&ctx, OsuVSAnnouncement::End, until).await;
+    }
+}
+
+async fn track_scores(ctx: &Context, osuvs_maps: &[OsuVSMap], now: DateTime<Utc>, backfill: bool) {
+    let users = match due_users(ctx, now, backfill).await {
+        Ok(users) => users,
+        Err(why) => {
+            unwind_error!(error, why, "Error while gathering users to track: {}");
 
-            users.push(user_ids[number]);
+            return;
         }
+    };
 
-        debug!("[Track] {} users: {:?}", users.len(), users);
-        loop_iteration(&ctx, map_id, &users, start).await;
+    debug!("[Track] {} users: {:?}", users.len(), users);
 
-        if end - now < ChronoDuration::seconds(OSUVS_TRACK_INTERVAL as i64) {
-            if let Err(why) = map_end(&ctx, map_id).await {
-                unwind_error!(error, why, "Error while announcing end of osuvs map: {}");
-            }
+    let mut modes: Vec<_> = osuvs_maps.iter().map(|osuvs_map| osuvs_map.mode).collect();
+    modes.dedup();
+
+    let mut failed = HashSet::new();
+
+    for mode in modes {
+        let scores = recent_scores(ctx, &users, mode, &mut failed).await;
+
+        for osuvs_map in osuvs_maps.iter().filter(|osuvs_map| osuvs_map.mode == mode) {
+            loop_iteration(ctx, osuvs_map, &scores).await;
         }
     }
+
+    // Users whose request failed will be checked again next iteration
+    let checked: Vec<_> = users
+        .into_iter()
+        .filter(|user_id| !failed.contains(user_id))
+        .collect();
+
+    if let Err(why) = ctx.database.update_osuvs_last_checked(&checked, now).await {
+        unwind_error!(
+            error,
+            why,
+            "Error while updating osuvs tracking timestamps: {}"
+        );
+    }
 }
 
-async fn loop_iteration(ctx: &Context, map_id: u32, users: &[u32], start: DateTime<Utc>) {
-    // Request the last 50 recents scores for all online tracked users
-    let scores: HashMap<u32, Vec<Score>> = {
-        users
-            .iter()
-            .map(|&user_id| {
-                ctx.osu
-                    .user_scores(user_id)
-                    .recent()
-                    .mode(GameMode::STD)
-                    .limit(100)
-                    .map(move |res| (user_id, res))
-            })
-            .collect::<FuturesUnordered<_>>()
-            .filter_map(|(user_id, res)| async move {
-                match res {
-                    Ok(scores) => Some((user_id, scores)),
-                    Err(why) => {
-                        unwind_error!(warn, why, "Error while requesting tracked user: {}");
-
-                        None
-                    }
-                }
-            })
-            .collect()
-            .await
+/// Send all announcements of the kind that are due until the given date
+async fn announce_all(ctx: &Context, kind: OsuVSAnnouncement, until: DateTime<Utc>) {
+    let osuvs_maps = match ctx.database.get_unannounced_osuvs_maps(kind, until).await {
+        Ok(osuvs_maps) => osuvs_maps,
+        Err(why) => {
+            unwind_error!(error, why, "Error while getting unannounced osuvs maps: {}");
+
+            return;
+        }
     };
 
-    // Map each user to a vec containing the best score
-    // on the osuvs map for each played mod
-    let recent_best: HashMap<_, _> = scores
+    for osuvs_map in osuvs_maps {
+        if let Err(why) = announce(ctx, &osuvs_map, kind).await {
+            unwind_error!(error, why, "Error while announcing osuvs map: {}");
+        }
+    }
+}
+
+/// Claim the announcement in the database before sending it so it is not sent
+/// twice. The claim is only marked as sent once sending succeeded. If sending
+/// fails, the claim is released again, and claims that are still pending
+/// because the bot stopped midway are released on the next boot.
+async fn announce(ctx: &Context, osuvs_map: &OsuVSMap, kind: OsuVSAnnouncement) -> BotResult<()> {
+    if !ctx
+        .database
+        .claim_osuvs_announcement(osuvs_map.map_id, kind)
+        .await?
+    {
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let late_threshold = ChronoDuration::seconds(2 * OSUVS_TRACK_INTERVAL as i64);
+
+    if kind == OsuVSAnnouncement::Start {
+        if let Err(why) = snapshot_divisions(ctx, osuvs_map).await {
+            ctx.database
+                .release_osuvs_announcement(osuvs_map.map_id, kind)
+                .await?;
+
+            return Err(why);
+        }
+    }
+
+    let result = match kind {
+        // Rounds that are already over only get their results posted
+        OsuVSAnnouncement::Start if osuvs_map.end_date <= now => Ok(()),
+        OsuVSAnnouncement::Start => {
+            let late = now - osuvs_map.start_date > late_threshold;
+
+            map_start(ctx, osuvs_map, late).await
+        }
+        OsuVSAnnouncement::End => {
+            let late = now - osuvs_map.end_date > late_threshold;
+
+            map_end(ctx, osuvs_map, late).await
+        }
+    };
+
+    if let Err(why) = result {
+        ctx.database
+            .release_osuvs_announcement(osuvs_map.map_id, kind)
+            .await?;
+
+        return Err(why);
+    }
+
+    ctx.database
+        .mark_osuvs_announcement_sent(osuvs_map.map_id, kind)
+        .await?;
+
+    if kind == OsuVSAnnouncement::End {
+        if let Err(why) = post_standings(ctx, osuvs_map).await {
+            unwind_error!(error, why, "Error while posting osuvs standings: {}");
+        }
+
+        if let Err(why) = champion_role(ctx, osuvs_map).await {
+            unwind_error!(
+                error,
+                why,
+                "Error while handing out the osuvs champion role: {}"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Linked users that should be checked during this iteration.
+/// Online users are checked more frequently than offline users.
+/// If the online state is unknown, users are treated as online.
+async fn due_users(ctx: &Context, now: DateTime<Utc>, backfill: bool) -> BotResult<Vec<u32>> {
+    let mut user_ids: Vec<_> = ctx
+        .database
+        .get_manual_links()
+        .await?
         .into_iter()
-        .filter_map(|(user_id, scores)| {
-            scores
-                .into_iter()
-                .filter(|s| s.map.as_ref().unwrap().map_id == map_id)
-                .filter(|s| s.grade(None) != Grade::F)
-                .filter(|s| s.created_at >= start)
-                .filter(|s| !s.mods.contains(GameMods::ScoreV2))
-                .max_by_key(|s| s.score)
-                .map(|s| (user_id, s))
+        .map(|(_, osu_id)| osu_id)
+        .collect();
+
+    user_ids.sort_unstable();
+    user_ids.dedup();
+
+    if backfill {
+        return Ok(user_ids);
+    }
+
+    let last_checked = ctx.database.get_osuvs_last_checked().await?;
+    let irc_connected = ctx.irc.is_connected();
+    let mut users = Vec::with_capacity(user_ids.len());
+
+    for user_id in user_ids {
+        let since_check = match last_checked.get(&user_id) {
+            Some(&date) => (now - date).num_seconds(),
+            None => {
+                users.push(user_id);
+
+                continue;
+            }
+        };
+
+        let due = if since_check >= OFFLINE_CHECK_INTERVAL {
+            true
+        } else if since_check >= ONLINE_CHECK_INTERVAL {
+            !irc_connected || is_online(ctx, user_id).await
+        } else {
+            false
+        };
+
+        if due {
+            users.push(user_id);
+        }
+    }
+
+    Ok(users)
+}
+
+async fn is_online(ctx: &Context, user_id: u32) -> bool {
+    if let Err(why) = ctx.links.track(&ctx.osu, &ctx.irc, user_id).await {
+        unwind_error!(warn, why, "Failed to retrieve username of tracked user: {}");
+
+        // Better check once too often than to miss a score
+        return true;
+    }
+
+    ctx.irc.online_since(user_id).is_some()
+}
+
+/// Request the last 100 recent scores of the users in the mode.
+/// Users whose request failed are added to `failed`.
+async fn recent_scores(
+    ctx: &Context,
+    users: &[u32],
+    mode: GameMode,
+    failed: &mut HashSet<u32>,
+) -> HashMap<u32, Vec<Score>> {
+    let mut scores = HashMap::with_capacity(users.len());
+
+    let mut requests: FuturesUnordered<_> = users
+        .iter()
+        .map(|&user_id| {
+            ctx.osu
+                .user_scores(user_id)
+                .recent()
+                .mode(mode)
+                .limit(100)
+                .map(move |res| (user_id, res))
+        })
+        .collect();
+
+    while let Some((user_id, res)) = requests.next().await {
+        match res {
+            Ok(user_scores) => {
+                scores.insert(user_id, user_scores);
+            }
+            Err(why) => {
+                unwind_error!(warn, why, "Error while requesting tracked user: {}");
+                failed.insert(user_id);
+            }
+        }
+    }
+
+    scores
+}
+
+async fn loop_iteration(ctx: &Context, osuvs_map: &OsuVSMap, scores: &HashMap<u32, Vec<Score>>) {
+    let map_id = osuvs_map.map_id;
+    let rules = &osuvs_map.rules;
+
+    // Map each user to their recent scores on the osuvs map that follow the rules
+    let mut valid_scores: HashMap<u32, Vec<Score>> = scores
+        .iter()
+        .map(|(&user_id, scores)| {
+            let scores: Vec<_> = scores
+                .iter()
+                .filter(|s| osuvs_map.accepts(s))
+                .cloned()
+                .collect();
+
+            (user_id, scores)
         })
+        .filter(|(_, scores)| !scores.is_empty())
         .collect();
 
     // If no one played the map, skip loop iteration
-    if recent_best.is_empty() {
+    if valid_scores.is_empty() {
         return;
     }
 
-    let total_best: HashMap<u32, Score> = match ctx.database.get_osuvs_highscores(200).await {
-        Ok(highscores) => highscores.into_iter().collect(),
+    // The pp are stored alongside the score so they don't need to be recalculated
+    if rules.uses_pp() {
+        let scores = valid_scores.values_mut().flatten();
+
+        if let Err(why) = calculate_pp(ctx, osuvs_map, scores).await {
+            unwind_error!(error, why, "Error while calculating pp of OsuVS scores: {}");
+
+            return;
+        }
+    }
+
+    let recent_best = valid_scores.into_iter().filter_map(|(user_id, scores)| {
+        scores
+            .into_iter()
+            .min_by(|a, b| rules.cmp(a, b))
+            .map(|s| (user_id, s))
+    });
+
+    let mut highscores = match ctx
+        .database
+        .get_osuvs_highscores(map_id, rules, usize::MAX)
+        .await
+    {
+        Ok(highscores) => highscores,
         Err(why) => {
             unwind_error!(error, why, "Error while getting OsuVS highscores: {}");
 
@@ -142,32 +368,37 @@ async fn loop_iteration(ctx: &Context, map_id: u32, users: &[u32], start: DateTi
     };
 
     for (user, score) in recent_best {
-        // Check if the new (mods,score) tuples are better than the previous ones
-        let new_score: Option<Score> = match total_best.get(&user) {
-            Some(s) => (score.score > s.score).then(|| score),
-            None => Some(score),
+        // Check if the new score is better than the previous one
+        let improved = match highscores.iter().find(|(user_id, _)| *user_id == user) {
+            Some((_, s)) => rules.cmp(&score, s) == Ordering::Less,
+            None => true,
         };
-        if let Some(score) = new_score {
-            if let Err(why) = ctx
-                .database
-                .insert_osuvs_highscores(map_id, user, score)
-                .await
-            {
-                unwind_error!(error, why, "Error while inserting new OsuVS scores: {}");
-            }
+
+        if !improved {
+            continue;
+        }
+
+        match submit_score(ctx, osuvs_map, &mut highscores, user, score.clone()).await {
+            Ok(placement) => notify_improvement(ctx, osuvs_map, user, &score, placement).await,
+            Err(why) => unwind_error!(error, why, "Error while inserting new OsuVS scores: {}"),
         }
     }
 }
 
-async fn map_start(ctx: &Context, map_id: u32, end: DateTime<Utc>) -> BotResult<()> {
+async fn map_start(ctx: &Context, osuvs_map: &OsuVSMap, late: bool) -> BotResult<()> {
     info!("Starting osuvs map, sending message...");
+    let map_id = osuvs_map.map_id;
     let map = ctx.osu.beatmap().map_id(map_id).await?;
     let title = map_to_string(&map);
     let url = format!("{}b/{}", OSU_BASE, map_id);
-    let author = Author::new("A new OsuVS has started!");
+    let author = Author::new(format!(
+        "A new {} OsuVS has started!",
+        mode_name(osuvs_map.mode)
+    ));
     let description = format!(
         "Stars: `{}★` Length: `{}` (`{}`) Combo: `{}x`\n\
-        CS: `{}` HP: `{}` OD: `{}` AR: `{}`",
+        CS: `{}` HP: `{}` OD: `{}` AR: `{}`\n\
+        {}",
         round(map.stars),
         sec_to_minsec(map.seconds_total),
         sec_to_minsec(map.seconds_drain),
@@ -175,23 +406,29 @@ async fn map_start(ctx: &Context, map_id: u32, end: DateTime<Utc>) -> BotResult<
         round(map.cs),
         round(map.hp),
         round(map.od),
-        round(map.ar)
+        round(map.ar),
+        osuvs_map.rules
     );
     let image = format!(
         "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
         map.mapset_id
     );
-    let footer = Footer::new(format!(
+    let mut footer = format!(
         "You can submit plays until {}",
-        (end - ChronoDuration::minutes(5)).format(OSUVS_DATE_FORMAT)
-    ));
+        (osuvs_map.end_date - ChronoDuration::minutes(5)).format(OSUVS_DATE_FORMAT)
+    );
+
+    if late {
+        footer.insert_str(0, "Posted late • ");
+    }
+
     let builder = EmbedBuilder::new()
         .title(title)
         .url(url)
         .author(author)
         .description(description)
         .image(image)
-        .footer(footer);
+        .footer(Footer::new(footer));
 
     ctx.http
         .create_message(OSUVS_CHANNEL)
@@ -203,46 +440,42 @@ async fn map_start(ctx: &Context, map_id: u32, end: DateTime<Utc>) -> BotResult<
     Ok(())
 }
 
-async fn map_end(ctx: &Context, map_id: u32) -> BotResult<()> {
+async fn map_end(ctx: &Context, osuvs_map: &OsuVSMap, late: bool) -> BotResult<()> {
     info!("Ending osuvs map, sending final message...");
+    let map_id = osuvs_map.map_id;
+    let mode = osuvs_map.mode;
     //? Send message to user in case of error?
     let map = ctx.osu.beatmap().map_id(map_id).await?;
+    let divisions = ctx.database.get_osuvs_map_divisions(map_id).await?;
+    let count = if divisions.is_some() { usize::MAX } else { 3 };
     //? Send message to user in case of error?
-    //? Maybe put this in get_osuvs_highscores function itself?
-    let highscores: Vec<_> = ctx.database.get_osuvs_highscores(3).await?;
-    let mut users: HashMap<_, _> = {
-        let user_reqs = highscores
-            .iter()
-            .map(|(user_id, _)| ctx.osu.user(*user_id).mode(GameMode::STD));
-
-        match try_join_all(user_reqs).await {
-            Ok(options) => options.into_iter().map(|u| (u.user_id, u)).collect(),
-            Err(why) => {
-                unwind_error!(
-                    error,
-                    why,
-                    "Error when retrieving users for osuvs highscores: {}"
-                );
-                HashMap::new()
-            }
-        }
-    };
-    let highscores: Vec<_> = highscores
-        .into_iter()
-        .map(|(user_id, score)| (users.remove(&user_id).unwrap(), score))
-        .collect();
+    let entries = osuvs_leaderboard(ctx, osuvs_map, count).await?;
 
     let title = map_to_string(&map);
     let url = format!("{}b/{}", OSU_BASE, map_id);
-    let author = Author::new("The OsuVS has ended! Here are the final results!");
+    let author = Author::new(format!(
+        "The {} OsuVS has ended! Here are the final results!",
+        mode_name(mode)
+    ));
+    let footer = late.then(|| {
+        Footer::new(format!(
+            "Posted late, the OsuVS ended on {}",
+            osuvs_map.end_date.format(OSUVS_DATE_FORMAT)
+        ))
+    });
 
-    if highscores.is_empty() {
+    if entries.is_empty() {
         let description = "No scores have been submitted this week! I am quite saddened by this :(";
-        let builder = EmbedBuilder::new()
+        let mut builder = EmbedBuilder::new()
             .title(title)
             .url(url)
             .author(author)
             .description(description);
+
+        if let Some(footer) = footer {
+            builder = builder.footer(footer);
+        }
+
         ctx.http
             .create_message(OSUVS_CHANNEL)
             .embeds(&[builder.build()])
@@ -251,55 +484,63 @@ async fn map_end(ctx: &Context, map_id: u32) -> BotResult<()> {
             .await?;
         return Ok(());
     }
-    let map_path = prepare_beatmap_file(map_id).await?;
-    let pp_map = Beatmap::from_path(map_path).await?;
     let mut description = String::new();
-    let mut attr_values: HashMap<GameMods, OsuPerformanceAttributes> = HashMap::new();
-    let thumbnail = format!("https://a.ppy.sh/{}", highscores[0].0.user_id);
+    let thumbnail = format!("https://a.ppy.sh/{}", entries[0].user_id);
+    let max_combo = map.max_combo.unwrap_or(0);
 
-    for ((user, score), i) in highscores.into_iter().zip(0..) {
-        let attributes = match attr_values.get(&score.mods) {
-            Some(attrs) => attrs.to_owned(),
-            None => OsuPP::new(&pp_map).mods(score.mods.bits()).calculate(),
-        };
+    let mut shown = 0;
+
+    match divisions {
+        // Every division gets its own podium
+        Some(divisions) => {
+            let members = ctx.database.get_osuvs_division_members(map_id).await?;
+
+            for division in 1..=divisions.count() {
+                let _ = writeln!(description, "__**{}**__", divisions.name(division));
 
-        let max_pp = attributes.pp();
-        let pp = OsuPP::new(&pp_map)
-            .attributes(attributes.difficulty.clone())
-            .mods(score.mods.bits())
-            .combo(score.max_combo as usize)
-            .misses(score.statistics.count_miss as usize)
-            .n300(score.statistics.count_300 as usize)
-            .n100(score.statistics.count_100 as usize)
-            .n50(score.statistics.count_50 as usize)
-            .calculate()
-            .pp();
-        attr_values.insert(score.mods, attributes);
-
-        let _ = writeln!(
+                let podium = entries
+                    .iter()
+                    .filter(|entry| members.get(&entry.user_id) == Some(&division))
+                    .zip(EMOTE_MEDALS.iter());
+
+                let prev_shown = shown;
+
+                for (entry, medal) in podium {
+                    write_entry(&mut description, medal, entry, max_combo);
+                    shown += 1;
+                }
+
+                if shown == prev_shown {
+                    description.push_str("No scores have been submitted in this division\n");
+                }
+
+                description.push('\n');
+            }
+        }
+        None => {
+            for (entry, medal) in entries.iter().zip(EMOTE_MEDALS.iter()) {
+                write_entry(&mut description, medal, entry, max_combo);
+                description.push('\n');
+                shown += 1;
+            }
+        }
+    }
+
+    let count = ctx.database.osuvs_score_count(map_id).await?;
+
+    if count > shown {
+        let _ = write!(
             description,
-            "{} {} [{}]({}users/{}): {} [ **{}x**/{}x ] **+{}**\n\
-            - **{}**/{}PP - {}% - <t:{}:R>\n",
-            EMOTE_MEDALS[i],
-            EMOTE_RANKS[&score.grade],
-            user.username,
-            OSU_BASE,
-            user.user_id,
-            with_comma_uint(score.score),
-            score.max_combo,
-            map.max_combo.unwrap_or(0),
-            score.mods,
-            round(pp as f32),
-            round(max_pp as f32),
-            round(score.accuracy),
-            &score.created_at.timestamp()
+            "**{}** more players took part, check out `/osuvs results {}` for the full leaderboard!",
+            count - shown,
+            map_id
         );
     }
     let image = format!(
         "https://assets.ppy.sh/beatmaps/{}/covers/cover.jpg",
         map.mapset_id
     );
-    let builder = EmbedBuilder::new()
+    let mut builder = EmbedBuilder::new()
         .title(title)
         .url(url)
         .thumbnail(thumbnail)
@@ -307,6 +548,10 @@ async fn map_end(ctx: &Context, map_id: u32) -> BotResult<()> {
         .description(description)
         .image(image);
 
+    if let Some(footer) = footer {
+        builder = builder.footer(footer);
+    }
+
     ctx.http
         .create_message(OSUVS_CHANNEL)
         .embeds(&[builder.build()])
@@ -316,3 +561,241 @@ async fn map_end(ctx: &Context, map_id: u32) -> BotResult<()> {
 
     Ok(())
 }
+
+/// Give the champion role to the winners of the latest round of each mode
+/// and take it from everyone else. If the winner of the ended round can't
+/// receive the role, it's logged in the approve channel.
+async fn champion_role(ctx: &Context, osuvs_map: &OsuVSMap) -> BotResult<()> {
+    let role = match ctx.champion_role {
+        Some(role) => role,
+        None => return Ok(()),
+    };
+
+    let past_maps = ctx.database.get_past_osuvs_maps().await?;
+    let latest = latest_rounds(osuvs_map, past_maps);
+
+    let mut champions = HashSet::new();
+    let mut winner = None;
+
+    for latest_map in latest {
+        let highscores = ctx
+            .database
+            .get_osuvs_highscores(latest_map.map_id, &latest_map.rules, 1)
+            .await?;
+
+        if let Some((user_id, score)) = highscores.first() {
+            champions.insert(*user_id);
+
+            if latest_map.map_id == osuvs_map.map_id {
+                winner = Some((*user_id, score_username(*user_id, score)));
+            }
+        }
+    }
+
+    let links = ctx.database.get_manual_links().await?;
+    let guild_id = SERVER_ID;
+    let mut members: Vec<Member> = Vec::new();
+
+    loop {
+        let mut req = ctx.http.guild_members(guild_id).limit(1000).unwrap();
+
+        if let Some(member) = members.last() {
+            req = req.after(member.user.id);
+        }
+
+        let page = req.exec().await?.models().await?;
+
+        if page.is_empty() {
+            break;
+        }
+
+        members.extend(page);
+    }
+
+    for member in members.iter() {
+        let is_champion =
+            matches!(links.get(&member.user.id), Some(osu_id) if champions.contains(osu_id));
+        let has_role = member.roles.contains(&role);
+
+        if is_champion && !has_role {
+            let req = ctx
+                .http
+                .add_guild_member_role(guild_id, member.user.id, role)
+                .exec()
+                .await;
+
+            if let Err(why) = req {
+                unwind_error!(
+                    error,
+                    why,
+                    "Could not add osuvs champion role to member: {}"
+                );
+            } else {
+                info!("Added 'OsuVS Champion' role to member {}", member.user.name);
+            }
+        } else if !is_champion && has_role {
+            let req = ctx
+                .http
+                .remove_guild_member_role(guild_id, member.user.id, role)
+                .exec()
+                .await;
+
+            if let Err(why) = req {
+                unwind_error!(
+                    error,
+                    why,
+                    "Could not remove osuvs champion role from member: {}"
+                );
+            } else {
+                info!(
+                    "Removed 'OsuVS Champion' role from member {}",
+                    member.user.name
+                );
+            }
+        }
+    }
+
+    let (winner_id, username) = match winner {
+        Some(winner) => winner,
+        None => return Ok(()),
+    };
+
+    let linked: Vec<_> = links
+        .iter()
+        .filter(|(_, osu_id)| **osu_id == winner_id)
+        .map(|(discord_id, _)| *discord_id)
+        .collect();
+
+    let reason = if linked.is_empty() {
+        "is not linked to a discord account"
+    } else if !members
+        .iter()
+        .any(|member| linked.contains(&member.user.id))
+    {
+        "is no longer in the server"
+    } else {
+        return Ok(());
+    };
+
+    let content = format!(
+        "[{}]({}users/{}) won the {} OsuVS but {} so they did not receive the champion role",
+        username,
+        OSU_BASE,
+        winner_id,
+        mode_name(osuvs_map.mode),
+        reason
+    );
+    let embed = EmbedBuilder::new().description(content).build();
+
+    ctx.http
+        .create_message(APPROVE_CHANNEL)
+        .embeds(&[embed])?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+/// The latest round of every mode. The round that just ended counts as the
+/// latest of its mode since it is announced shortly before its end date.
+fn latest_rounds(ended: &OsuVSMap, past_maps: Vec<OsuVSMap>) -> Vec<OsuVSMap> {
+    let mut latest = vec![*ended];
+
+    // Ordered by end date so the first round of a mode is its latest
+    for past_map in past_maps {
+        if latest
+            .iter()
+            .all(|latest_map| latest_map.mode != past_map.mode)
+        {
+            latest.push(past_map);
+        }
+    }
+
+    latest
+}
+
+/// Post the standings of the season that the osuvs map belongs to
+async fn post_standings(ctx: &Context, osuvs_map: &OsuVSMap) -> BotResult<()> {
+    let season = match ctx
+        .database
+        .get_osuvs_season_at(osuvs_map.start_date)
+        .await?
+    {
+        Some(season) => season,
+        None => return Ok(()),
+    };
+
+    info!("Posting osuvs standings of season {}...", season.name);
+    let mode = osuvs_map.mode;
+    let end_date = osuvs_map.end_date;
+
+    // Rounds with divisions post the standings of each division
+    let divisions: Vec<_> = match ctx
+        .database
+        .get_osuvs_map_divisions(osuvs_map.map_id)
+        .await?
+    {
+        Some(divisions) => (1..=divisions.count()).map(Some).collect(),
+        None => vec![None],
+    };
+
+    let mut embeds = Vec::with_capacity(divisions.len());
+
+    for division in divisions {
+        let standings = season_standings(ctx, &season, mode, end_date, division).await?;
+        let pages = Pages::new(10, standings.len());
+        let embed = standings_embed(&season, mode, division, &standings, &pages).build();
+        embeds.push(embed);
+    }
+
+    // Discord only allows a limited amount of embeds per message
+    for chunk in embeds.chunks(EMBEDS_PER_MESSAGE) {
+        ctx.http
+            .create_message(OSUVS_CHANNEL)
+            .embeds(chunk)?
+            .exec()
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::database::OsuVSRules;
+
+    fn osuvs_map(map_id: u32, mode: GameMode, end_date: DateTime<Utc>) -> OsuVSMap {
+        OsuVSMap {
+            map_id,
+            mode,
+            rules: OsuVSRules::default(),
+            start_date: end_date - ChronoDuration::days(7),
+            end_date,
+        }
+    }
+
+    #[test]
+    fn test_latest_rounds_before_end_date() {
+        let now = Utc::now();
+
+        // Announced before its end date so it is not a past round yet
+        let ended = osuvs_map(3, GameMode::STD, now + ChronoDuration::seconds(30));
+
+        // Ordered by end date, latest first
+        let past_maps = vec![
+            osuvs_map(4, GameMode::MNA, now - ChronoDuration::days(2)),
+            osuvs_map(2, GameMode::STD, now - ChronoDuration::days(7)),
+            osuvs_map(5, GameMode::MNA, now - ChronoDuration::days(9)),
+            osuvs_map(1, GameMode::STD, now - ChronoDuration::days(14)),
+        ];
+
+        let latest: Vec<_> = latest_rounds(&ended, past_maps)
+            .into_iter()
+            .map(|osuvs_map| osuvs_map.map_id)
+            .collect();
+
+        assert_eq!(latest, vec![3, 4]);
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{
+    context::Context,
+    error::BotResult,
+    utils::{EmbedBuilder, OSU_BASE},
+};
+
+/// Players that come online again within this time are not announced again
+const ANNOUNCE_COOLDOWN: Duration = Duration::from_secs(1800);
+
+/// Announce tracked players that come online in the feed channel
+/// and notify the discord users that follow them
+pub async fn presence_feed(ctx: Arc<Context>, mut joins: UnboundedReceiver<u32>) {
+    let mut announced: HashMap<u32, Instant> = HashMap::new();
+
+    while let Some(osu_id) = joins.recv().await {
+        // Bancho regularly drops connections which shouldn't spam everyone
+        if matches!(announced.get(&osu_id), Some(last) if last.elapsed() < ANNOUNCE_COOLDOWN) {
+            continue;
+        }
+
+        announced.insert(osu_id, Instant::now());
+
+        if let Err(why) = announce_online(&ctx, osu_id).await {
+            unwind_error!(warn, why, "Error while announcing online player: {}");
+        }
+    }
+}
+
+async fn announce_online(ctx: &Context, osu_id: u32) -> BotResult<()> {
+    if ctx.database.get_presence_hidden().await?.contains(&osu_id) {
+        return Ok(());
+    }
+
+    let username = match ctx.links.username(osu_id) {
+        Some(username) => username,
+        None => return Ok(()),
+    };
+
+    let description = format!("[{}]({}users/{}) is now online", username, OSU_BASE, osu_id);
+    let embed = EmbedBuilder::new()
+        .thumbnail(format!("https://a.ppy.sh/{}", osu_id))
+        .description(description)
+        .build();
+
+    if let Some(channel) = ctx.online_feed {
+        ctx.http
+            .create_message(channel)
+            .embeds(std::slice::from_ref(&embed))?
+            .exec()
+            .await?;
+    }
+
+    for follower in ctx.database.get_presence_followers(osu_id).await? {
+        if let Err(why) = ctx.send_dm(follower, embed.clone()).await {
+            unwind_error!(
+                warn,
+                why,
+                "Could not notify user {} about online player: {}",
+                follower
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -14,32 +14,39 @@ macro_rules! unwind_error {
     };
 }
 
+mod beatmap_store;
 mod commands;
 mod context;
 mod database;
+mod difficulty_cache;
 mod error;
+mod link_manager;
 mod logging;
 mod loops;
 mod osu_irc;
 mod stats;
 mod utils;
 
+use beatmap_store::BeatmapStore;
 use context::Context;
 use database::Database;
+use difficulty_cache::DifficultyCache;
 use error::{BotResult, Error};
+use link_manager::LinkManager;
 
 use futures::StreamExt;
 use osu_irc::IrcClient;
-use rosu_v2::{prelude::OsuError, Osu};
+use rosu_v2::Osu;
 use stats::BotStats;
 use std::{env, sync::Arc, time::Duration};
-use tokio::time::sleep;
+use tokio::{sync::mpsc, time::sleep};
 use twilight_cache_inmemory::{InMemoryCache, ResourceType};
 use twilight_gateway::{cluster::Events, Cluster, Event, EventTypeFlags, Intents};
 use twilight_http::Client as HttpClient;
 use twilight_model::{
     application::interaction::Interaction,
     gateway::presence::{ActivityType, Status},
+    id::{ChannelId, RoleId},
 };
 use twilight_standby::Standby;
 use utils::{
@@ -48,8 +55,8 @@ use utils::{
 
 use crate::{
     commands::handle_interaction,
-    loops::{background_loop, osu_tracking},
-    utils::{conc_map::SyncRwLockMap, osu::username_to_number, GENERAL_CHANNEL, SERVER_ID},
+    loops::{background_loop, osu_tracking, presence_feed},
+    utils::{GENERAL_CHANNEL, SERVER_ID},
 };
 
 #[macro_use]
@@ -144,25 +151,6 @@ async fn async_main() -> BotResult<()> {
 
     let osu = Osu::new(client_id, client_secret).await?;
 
-    // TODO: DashSet should contain list of users to track
-    let targets = SyncRwLockMap::default();
-    let members = database.get_manual_links().await?;
-    for (_, osu_id) in members {
-        match osu.user(osu_id).await {
-            Ok(user) => {
-                let number = username_to_number(&user.username);
-                targets.write(number).insert(false)
-            }
-            Err(OsuError::NotFound) => println!("User with osu_id {} was not found", osu_id),
-            Err(why) => unwind_error!(
-                error,
-                why,
-                "Error when retrieving usernames from manual links: {}"
-            ),
-        }
-    }
-    debug!("{:#?}", targets);
-
     // IRC
     let server = env::var("IRC_SERVER").expect("Could not load IRC_SERVER");
     let port = env::var("IRC_PORT")
@@ -171,18 +159,20 @@ async fn async_main() -> BotResult<()> {
         .expect("IRC_PORT expected to be a u16");
     let nickname = env::var("IRC_NICKNAME").expect("Could not load IRC_NICKNAME");
     let password = env::var("IRC_PASSWORD").expect("Could not load IRC_PASSWORD");
-    let irc = Arc::new(IrcClient::new(targets));
-    let irc_clone = Arc::clone(&irc);
-
-    // Boot up IRC client
-    tokio::spawn(async move {
-        loop {
-            if let Err(why) = irc_clone.run(&server, port, &nickname, &password).await {
-                unwind_error!(error, why, "Error while running IRC: {}");
-            }
-
-            sleep(Duration::from_secs(1)).await;
-        }
+    let (presence_tx, presence_rx) = mpsc::unbounded_channel();
+    let irc = Arc::new(IrcClient::new(presence_tx));
+    let links = LinkManager::new(&osu, &database, &irc).await?;
+
+    // Optional settings
+    let champion_role = env::var("OSUVS_CHAMPION_ROLE_ID").ok().map(|id| {
+        id.parse()
+            .map(RoleId)
+            .expect("OSUVS_CHAMPION_ROLE_ID expected to be a u64")
+    });
+    let online_feed = env::var("ONLINE_FEED_CHANNEL_ID").ok().map(|id| {
+        id.parse()
+            .map(ChannelId)
+            .expect("ONLINE_FEED_CHANNEL_ID expected to be a u64")
     });
 
     // let trackdata = RwLock::new(None);
@@ -191,22 +181,39 @@ async fn async_main() -> BotResult<()> {
 
     let stats = BotStats::new(osu.metrics());
 
+    let beatmap_store = BeatmapStore::new().await?;
+
     let ctx = Context {
+        beatmap_store,
         cache,
+        champion_role,
         cluster,
         database,
+        difficulty_cache: DifficultyCache::default(),
         http,
         irc,
+        links,
+        online_feed,
         osu,
         standby,
         stats,
     };
 
     let ctx = Arc::new(ctx);
+    let irc_ctx = Arc::clone(&ctx);
+
+    // Boot up IRC client
+    tokio::spawn(async move {
+        irc_ctx
+            .irc
+            .supervise(&irc_ctx.stats, &server, port, &nickname, &password)
+            .await
+    });
 
     tokio::select! {
         _ = background_loop(Arc::clone(&ctx)) => {}
         _ = osu_tracking(Arc::clone(&ctx)) => {}
+        _ = presence_feed(Arc::clone(&ctx), presence_rx) => {}
         _ = event_loop(Arc::clone(&ctx), events) => {}
         _ = wait_for_ctrl_c() => {}
     };
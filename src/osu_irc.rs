@@ -1,24 +1,179 @@
 use crate::{
-    utils::{conc_map::SyncRwLockMap, osu::username_to_number},
+    stats::BotStats,
+    utils::{
+        conc_map::SyncRwLockMap,
+        osu::{username_to_number, ExponentialBackoff},
+    },
     BotResult,
 };
+use chrono::{DateTime, Utc};
 use cow_utils::CowUtils;
 use futures::stream::StreamExt;
-use irc::client::prelude::*;
+use irc::client::{prelude::*, Sender};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+use tokio::{sync::mpsc::UnboundedSender, time::sleep};
+
+/// Seconds without activity until the connection is pinged
+const PING_TIME: u32 = 60;
+/// Seconds to wait for a pong until the connection is considered dead
+const PING_TIMEOUT: u32 = 30;
+/// Connections that lasted this long reset the reconnect delay
+const STABLE_CONNECTION: Duration = Duration::from_secs(600);
 
 pub struct IrcClient {
-    // Tracked users
-    pub targets: SyncRwLockMap<u128, bool>,
+    // Since when tracked users are online by their osu! id, `None` if they're offline
+    pub targets: SyncRwLockMap<u32, Option<DateTime<Utc>>>,
+    // Online dates from before the connection was lost, restored for
+    // users that are still online once the presence is synced again
+    stale: Mutex<HashMap<u32, DateTime<Utc>>>,
+    // Osu! id of tracked users by their normalized username as number
+    pub names: SyncRwLockMap<u128, u32>,
+    // Whether the online state of the targets can currently be trusted
+    pub connected: AtomicBool,
+    // Amount of successful connections, increases on every reconnect
+    pub connections: AtomicUsize,
+    // Sender of the current connection to send private messages
+    sender: Mutex<Option<Sender>>,
+    // Receives the osu! id of tracked users that joined
+    presence: UnboundedSender<u32>,
 }
 
 impl IrcClient {
-    #[inline]
-    pub fn new(targets: SyncRwLockMap<u128, bool>) -> Self {
-        IrcClient { targets }
+    pub fn new(presence: UnboundedSender<u32>) -> Self {
+        Self {
+            targets: SyncRwLockMap::default(),
+            names: SyncRwLockMap::default(),
+            stale: Mutex::new(HashMap::new()),
+            connected: AtomicBool::new(false),
+            connections: AtomicUsize::new(0),
+            sender: Mutex::new(None),
+            presence,
+        }
+    }
+
+    /// Since when the tracked user is online
+    pub fn online_since(&self, user_id: u32) -> Option<DateTime<Utc>> {
+        self.targets.read(user_id).get().copied().flatten()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Acquire)
     }
 
-    pub async fn run(
+    /// Keep a connection to Bancho alive. Reconnects are delayed with
+    /// an exponential backoff that is reset once a connection is stable.
+    pub async fn supervise(
         &self,
+        stats: &BotStats,
+        server: &str,
+        port: u16,
+        nickname: &str,
+        password: &str,
+    ) {
+        let new_backoff = || ExponentialBackoff::new(2).factor(1000).max_delay(300_000);
+        let mut backoff = new_backoff();
+
+        loop {
+            let start = Instant::now();
+            let result = self.listen(stats, server, port, nickname, password).await;
+            self.disconnect(stats);
+
+            match result {
+                Ok(_) => warn!("[IRC] Disconnected from Bancho"),
+                Err(why) => unwind_error!(warn, why, "[IRC] Lost connection to Bancho: {}"),
+            }
+
+            if start.elapsed() >= STABLE_CONNECTION {
+                backoff = new_backoff();
+            }
+
+            let delay = backoff.next().unwrap();
+            info!("[IRC] Reconnecting in {:?}...", delay);
+            sleep(delay).await;
+            stats.irc.reconnects.inc();
+        }
+    }
+
+    /// Send a private message to the osu! user.
+    /// Returns `false` if there is currently no connection to Bancho.
+    #[allow(clippy::result_large_err)]
+    pub fn send_private_message(&self, username: &str, message: &str) -> BotResult<bool> {
+        let sender = match self.sender.lock().clone() {
+            Some(sender) => sender,
+            None => return Ok(false),
+        };
+
+        // Spaces in usernames are replaced with underscores on IRC
+        sender.send_privmsg(username.cow_replace(' ', "_"), message)?;
+
+        Ok(true)
+    }
+
+    /// Online states can't be trusted without connection so everyone is
+    /// considered offline until the presence is synced after reconnecting.
+    /// The online dates are put aside so that users who stayed online
+    /// keep their original date and are not announced again.
+    fn disconnect(&self, stats: &BotStats) {
+        self.connected.store(false, Ordering::Release);
+        self.sender.lock().take();
+        stats.irc.connected.set(0);
+
+        let user_ids: Vec<_> = self.targets.iter().map(|(&user_id, _)| user_id).collect();
+        let mut dates = Vec::new();
+
+        for user_id in user_ids {
+            if let Some(since) = self.targets.write(user_id).get_mut() {
+                if let Some(date) = since.take() {
+                    dates.push((user_id, date));
+                }
+            }
+        }
+
+        self.stale.lock().extend(dates);
+    }
+
+    /// Osu! id of the tracked user with the IRC name, if any
+    fn tracked_id(&self, name: &str) -> Option<u32> {
+        self.names.read(username_to_number(name)).get().copied()
+    }
+
+    /// Update the online state of the tracked user. Returns `true` if the
+    /// user was tracked and their state changed. Users that were online
+    /// before the connection was lost get their previous date back instead.
+    fn set_online(&self, user_id: u32, name: &str, online: bool) -> bool {
+        let mut target = self.targets.write(user_id);
+
+        let since = match target.get_mut() {
+            Some(since) if since.is_some() != online => since,
+            _ => return false,
+        };
+
+        if online {
+            if let Some(date) = self.stale.lock().remove(&user_id) {
+                *since = Some(date);
+
+                return false;
+            }
+        }
+
+        info!(
+            "[IRC] {} now {}",
+            name,
+            if online { "online" } else { "offline" }
+        );
+        *since = online.then(Utc::now);
+
+        true
+    }
+
+    async fn listen(
+        &self,
+        stats: &BotStats,
         server: &str,
         port: u16,
         nickname: &str,
@@ -31,6 +186,8 @@ impl IrcClient {
             nickname: Some(nickname.to_owned()),
             channels: vec!["#osu".to_owned()],
             use_tls: Some(false),
+            ping_time: Some(PING_TIME),
+            ping_timeout: Some(PING_TIMEOUT),
             ..Default::default()
         };
 
@@ -38,38 +195,65 @@ impl IrcClient {
         client.identify()?;
 
         let mut stream = client.stream()?;
+        *self.sender.lock() = Some(client.sender());
+
+        info!("[IRC] Connected to Bancho, syncing presence...");
 
-        info!("[IRC] Connected to Bancho");
-        debug!("{:?}", self.targets);
         while let Some(msg) = stream.next().await.transpose()? {
-            // info!("{:#?}", msg);
             match msg.command {
                 Command::JOIN(..) => {
-                    if let Some(Prefix::Nickname(mut name, ..)) = msg.prefix {
-                        let number: u128 = username_to_number(&name);
+                    if let Some(Prefix::Nickname(name, ..)) = msg.prefix {
+                        if let Some(user_id) = self.tracked_id(&name) {
+                            if self.set_online(user_id, &name, true) {
+                                let _ = self.presence.send(user_id);
+                            }
+                        }
+                    }
+                }
+                Command::QUIT(reason) => {
+                    if let Some(Prefix::Nickname(name, ..)) = msg.prefix {
+                        let reason = reason.as_deref().unwrap_or("no reason");
+
+                        // Bancho disconnects us e.g. if the account logs in elsewhere
+                        if name.eq_ignore_ascii_case(nickname) {
+                            warn!("[IRC] Bancho closed the connection: {}", reason);
+
+                            return Ok(());
+                        }
 
-                        if matches!(self.targets.read(number).get(), Some(false)) {
-                            info!("[IRC] {} now online", name);
+                        debug!("[IRC] {} quit: {}", name, reason);
 
-                            if let Some(online) = self.targets.write(number).get_mut() {
-                                *online = true
-                            };
+                        if let Some(user_id) = self.tracked_id(&name) {
+                            self.set_online(user_id, &name, false);
                         }
                     }
                 }
-                Command::QUIT(..) => {
-                    if let Some(Prefix::Nickname(mut name, ..)) = msg.prefix {
-                        let number: u128 = username_to_number(&name);
+                Command::ERROR(reason) => {
+                    warn!("[IRC] Received error from Bancho: {}", reason);
 
-                        if matches!(self.targets.read(number).get(), Some(true)) {
-                            info!("[IRC] {} now offline", name);
+                    return Ok(());
+                }
+                // Everyone in #osu is listed after joining. Users that were
+                // online before already are not announced as coming online.
+                Command::Response(Response::RPL_NAMREPLY, args) => {
+                    if let Some(names) = args.last() {
+                        for name in names.split(' ') {
+                            let name = name.trim_start_matches(&['@', '+'][..]);
 
-                            if let Some(online) = self.targets.write(number).get_mut() {
-                                *online = false
-                            };
+                            if let Some(user_id) = self.tracked_id(name) {
+                                self.set_online(user_id, name, true);
+                            }
                         }
                     }
                 }
+                Command::Response(Response::RPL_ENDOFNAMES, _) if !self.is_connected() => {
+                    // Users that were not listed stay offline
+                    self.stale.lock().clear();
+                    info!("[IRC] Synced presence of tracked users");
+                    self.connections.fetch_add(1, Ordering::AcqRel);
+                    self.connected.store(true, Ordering::Release);
+                    stats.irc.connected.set(1);
+                }
                 _ => {}
             }
         }
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use prometheus::{IntCounter, IntCounterVec, Opts, Registry};
+use prometheus::{IntCounter, IntCounterVec, IntGauge, Opts, Registry};
 
 pub struct EventStats {
     pub channel_create: IntCounter,
@@ -44,6 +44,11 @@ pub struct CommandCounters {
     pub slash_commands: IntCounterVec,
 }
 
+pub struct IrcStats {
+    pub connected: IntGauge,
+    pub reconnects: IntCounter,
+}
+
 pub struct BotStats {
     pub registry: Registry,
     pub start_time: DateTime<Utc>,
@@ -51,6 +56,7 @@ pub struct BotStats {
     pub message_counts: MessageCounters,
     pub command_counts: CommandCounters,
     pub osu_metrics: OsuCounters,
+    pub irc: IrcStats,
 }
 
 macro_rules! metric_vec {
@@ -69,12 +75,17 @@ impl BotStats {
         let msg_counter = metric_vec!(counter: "messages", "Received messages", "sender_type");
         let slash_commands =
             metric_vec!(counter: "slash_commands", "Executed slash commands", "name");
+        let irc_connected =
+            IntGauge::new("irc_connected", "Whether the bot is connected to Bancho").unwrap();
+        let irc_reconnects = IntCounter::new("irc_reconnects", "Reconnects to Bancho").unwrap();
 
         let registry = Registry::new_custom(Some(String::from("bathbot")), None).unwrap();
         registry.register(Box::new(event_counter.clone())).unwrap();
         registry.register(Box::new(msg_counter.clone())).unwrap();
         registry.register(Box::new(slash_commands.clone())).unwrap();
         registry.register(Box::new(osu_metrics.clone())).unwrap();
+        registry.register(Box::new(irc_connected.clone())).unwrap();
+        registry.register(Box::new(irc_reconnects.clone())).unwrap();
 
         Self {
             registry,
@@ -114,6 +125,10 @@ impl BotStats {
             },
             command_counts: CommandCounters { slash_commands },
             osu_metrics: OsuCounters { rosu: osu_metrics },
+            irc: IrcStats {
+                connected: irc_connected,
+                reconnects: irc_reconnects,
+            },
         }
     }
 
@@ -52,7 +52,6 @@ impl<'c> MessageBuilder<'c> {
         self
     }
 
-    #[allow(dead_code)]
     pub fn components(mut self, components: &'c [Component]) -> Self {
         self.components.replace(components);
 
@@ -85,6 +84,12 @@ impl IntoEmbed for Embed {
     }
 }
 
+impl IntoEmbed for EmbedBuilder {
+    fn into_embed(self) -> Embed {
+        self.build()
+    }
+}
+
 impl IntoEmbed for String {
     fn into_embed(self) -> Embed {
         EmbedBuilder::new().description(self).build()
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use chrono::{DateTime, Datelike, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone, Utc};
 
 use super::DATE_FORMAT;
 
@@ -8,6 +8,19 @@ pub fn date_to_string(date: &DateTime<Utc>) -> String {
     date.format(DATE_FORMAT).to_string()
 }
 
+/// Parse a UTC date of the form `YYYY-MM-DD` or `YYYY-MM-DD HH:MM`
+pub fn parse_utc_date(date: &str) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+
+    if let Ok(date) = NaiveDateTime::parse_from_str(date, "%F %R") {
+        return Some(Utc.from_utc_datetime(&date));
+    }
+
+    NaiveDate::parse_from_str(date, "%F")
+        .ok()
+        .map(|date| Utc.from_utc_date(&date).and_hms(0, 0, 0))
+}
+
 pub fn sec_to_minsec(secs: u32) -> SecToMinSecFormatter {
     SecToMinSecFormatter { secs }
 }
@@ -60,12 +60,33 @@ impl ApplicationCommandExt for ApplicationCommand {
         builder: impl Into<MessageBuilder<'l>> + Send + 'l,
     ) -> BotResult<()> {
         let builder = builder.into();
+
+        // Files can't be attached to the callback so the response is
+        // deferred and the file is attached when updating it instead
+        if builder.file.is_some() {
+            let response = InteractionResponse::DeferredChannelMessageWithSource(CallbackData {
+                allowed_mentions: None,
+                components: None,
+                content: None,
+                embeds: vec![],
+                flags: builder.ephemeral.then_some(MessageFlags::EPHEMERAL),
+                tts: None,
+            });
+
+            ctx.http
+                .interaction_callback(self.id, &self.token, &response)
+                .exec()
+                .await?;
+
+            return self.update_message(ctx, builder).await;
+        }
+
         let response = InteractionResponse::ChannelMessageWithSource(CallbackData {
             allowed_mentions: None,
-            components: None,
+            components: builder.components.map(<[_]>::to_vec),
             content: builder.content.map(Cow::into_owned),
             embeds: builder.embed.map_or_else(Vec::new, |e| vec![e]),
-            flags: builder.ephemeral.then(|| MessageFlags::EPHEMERAL),
+            flags: builder.ephemeral.then_some(MessageFlags::EPHEMERAL),
             tts: None,
         });
 
@@ -102,12 +123,18 @@ impl ApplicationCommandExt for ApplicationCommand {
     ) -> BotResult<()> {
         let builder = builder.into();
 
-        ctx.http
+        let mut req = ctx
+            .http
             .update_interaction_original(&self.token)?
             .content(builder.content.as_deref())?
             .embeds(builder.embed.as_ref().map(std::slice::from_ref))?
-            .exec()
-            .await?;
+            .components(builder.components)?;
+
+        if let Some(file) = builder.file.as_ref() {
+            req = req.files(std::slice::from_ref(file));
+        }
+
+        req.exec().await?;
 
         Ok(())
     }
@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+
+use crate::{context::Context, utils::MessageBuilder, BotResult, Error};
+
+use twilight_model::{
+    application::{
+        callback::{CallbackData, InteractionResponse},
+        interaction::MessageComponentInteraction,
+    },
+    channel::message::MessageFlags,
+    id::UserId,
+};
+
+#[async_trait]
+pub trait MessageComponentExt {
+    #[allow(clippy::result_large_err)]
+    fn user_id(&self) -> BotResult<UserId>;
+    async fn create_message<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()>;
+    async fn update_message<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()>;
+}
+
+#[async_trait]
+impl MessageComponentExt for MessageComponentInteraction {
+    fn user_id(&self) -> BotResult<UserId> {
+        self.author_id().ok_or(Error::MissingSlashAuthor)
+    }
+
+    async fn create_message<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()> {
+        let builder = builder.into();
+        let response = InteractionResponse::ChannelMessageWithSource(CallbackData {
+            allowed_mentions: None,
+            components: builder.components.map(<[_]>::to_vec),
+            content: builder.content.map(Cow::into_owned),
+            embeds: builder.embed.map_or_else(Vec::new, |e| vec![e]),
+            flags: builder.ephemeral.then_some(MessageFlags::EPHEMERAL),
+            tts: None,
+        });
+
+        ctx.http
+            .interaction_callback(self.id, &self.token, &response)
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Edit the message that the component is attached to
+    async fn update_message<'l>(
+        &'l self,
+        ctx: &'l Context,
+        builder: impl Into<MessageBuilder<'l>> + Send + 'l,
+    ) -> BotResult<()> {
+        let builder = builder.into();
+        let response = InteractionResponse::UpdateMessage(CallbackData {
+            allowed_mentions: None,
+            components: Some(builder.components.map_or_else(Vec::new, <[_]>::to_vec)),
+            content: builder.content.map(Cow::into_owned),
+            embeds: builder.embed.map_or_else(Vec::new, |e| vec![e]),
+            flags: None,
+            tts: None,
+        });
+
+        ctx.http
+            .interaction_callback(self.id, &self.token, &response)
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
 mod application_command;
+mod message_component;
 
 pub use application_command::ApplicationCommandExt;
+pub use message_component::MessageComponentExt;
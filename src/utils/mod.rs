@@ -6,12 +6,14 @@ mod ext;
 pub mod matcher;
 pub mod numbers;
 pub mod osu;
+pub mod osuvs;
+pub mod pagination;
 
 pub use builders::author::Author;
 pub use builders::embed::EmbedBuilder;
 pub use builders::footer::Footer;
 pub use builders::message::MessageBuilder;
-pub use ext::ApplicationCommandExt;
+pub use ext::{ApplicationCommandExt, MessageComponentExt};
 use hashbrown::HashMap;
 use rosu_v2::prelude::Grade;
 use twilight_model::id::{ChannelId, GuildId, RoleId};
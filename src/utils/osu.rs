@@ -1,11 +1,20 @@
-use std::{path::PathBuf, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use bytes::Bytes;
 use regex::Regex;
-use rosu_v2::prelude::Beatmap;
-use tokio::{fs::File, io::AsyncWriteExt, time::sleep};
-
-use crate::error::MapDownloadError;
+use rosu_pp::{
+    Beatmap as PpBeatmap, DifficultyAttributes, FruitsPP, ManiaPP, OsuPP, PerformanceAttributes,
+    TaikoPP,
+};
+use rosu_v2::prelude::{Beatmap, GameMode, GameMods, Score};
+use tokio::time::sleep;
+
+use crate::{
+    beatmap_store::BeatmapFile,
+    context::Context,
+    difficulty_cache::calculate_difficulty,
+    error::{BotResult, MapDownloadError},
+};
 
 use super::OSU_BASE;
 
@@ -41,29 +50,26 @@ pub fn map_to_string(map: &Beatmap) -> String {
     format!("{} - {} [{}]", mapset.artist, mapset.title, map.version)
 }
 
-pub async fn prepare_beatmap_file(map_id: u32) -> Result<String, MapDownloadError> {
-    let mut map_path = PathBuf::new();
-    map_path.push(
-        std::env::var("BEATMAPS_FOLDER").expect("Missing environment variable (BEATMAPS_FOLDER)."),
-    );
-    map_path.push(format!("{map_id}.osu"));
-
-    if !map_path.exists() {
-        let content = request_beatmap_file(map_id).await?;
-        let mut file = File::create(&map_path).await?;
-        file.write_all(&content).await?;
-        info!("Downloaded {map_id}.osu successfully");
+pub fn mode_name(mode: GameMode) -> &'static str {
+    match mode {
+        GameMode::STD => "osu!standard",
+        GameMode::TKO => "osu!taiko",
+        GameMode::CTB => "osu!catch",
+        GameMode::MNA => "osu!mania",
     }
+}
 
-    let map_path = map_path
-        .into_os_string()
-        .into_string()
-        .expect("map_path OsString is no valid String");
-
-    Ok(map_path)
+pub fn parse_mode(mode: &str) -> Option<GameMode> {
+    match mode {
+        "osu" => Some(GameMode::STD),
+        "taiko" => Some(GameMode::TKO),
+        "fruits" => Some(GameMode::CTB),
+        "mania" => Some(GameMode::MNA),
+        _ => None,
+    }
 }
 
-async fn request_beatmap_file(map_id: u32) -> Result<Bytes, MapDownloadError> {
+pub async fn request_beatmap_file(map_id: u32) -> Result<Bytes, MapDownloadError> {
     let url = format!("{OSU_BASE}osu/{map_id}");
     let mut content = reqwest::get(&url).await?.bytes().await?;
 
@@ -137,11 +143,200 @@ impl Iterator for ExponentialBackoff {
     }
 }
 
+/// Case insensitive number of the username. Spaces and underscores are
+/// considered the same since IRC replaces spaces with underscores.
 pub fn username_to_number(name: &str) -> u128 {
     name.bytes()
-        .map(|byte| byte.to_ascii_lowercase())
+        .map(|byte| match byte {
+            b' ' => b'_',
+            _ => byte.to_ascii_lowercase(),
+        })
         .fold(Some(0_u128), |num, next| {
             num?.checked_shl(8)?.checked_add(next as u128)
         })
         .unwrap_or(0)
 }
+
+/// Calculates the pp of scores on a map in a given mode. The difficulty
+/// attributes of all mods are retrieved through the difficulty cache.
+///
+/// The calculator is chosen based on the mode instead of the map
+/// so that converted maps are handled as well.
+pub struct PpCalculator {
+    map: Arc<PpBeatmap>,
+    mode: GameMode,
+    attributes: HashMap<GameMods, DifficultyAttributes>,
+}
+
+impl PpCalculator {
+    /// Load the map and prepare the difficulty attributes for the given mods.
+    /// The map is only read and parsed if the cached one doesn't match the checksum.
+    /// Without checksum, the beatmap store requests it from the osu! API if necessary.
+    pub async fn new(
+        ctx: &Context,
+        map_id: u32,
+        checksum: Option<&str>,
+        mode: GameMode,
+        mods: &[GameMods],
+    ) -> BotResult<Self> {
+        let cache = &ctx.difficulty_cache;
+        let cached = checksum.and_then(|checksum| cache.map(map_id, checksum));
+
+        let (map, checksum) = match (cached, checksum) {
+            (Some(map), Some(checksum)) => (map, checksum.to_owned()),
+            _ => {
+                let BeatmapFile { content, checksum } =
+                    ctx.beatmap_store.get(&ctx.osu, map_id, checksum).await?;
+                let map = Arc::new(PpBeatmap::parse(content.as_slice()).await?);
+                cache.insert_map(map_id, &checksum, Arc::clone(&map));
+
+                (map, checksum)
+            }
+        };
+
+        cache.validate(&ctx.database, map_id, &checksum).await?;
+
+        let mut attributes = HashMap::new();
+
+        for &mods in mods {
+            if attributes.contains_key(&mods) {
+                continue;
+            }
+
+            let difficulty = cache
+                .get(&ctx.database, &map, map_id, &checksum, mods, mode)
+                .await?;
+
+            attributes.insert(mods, difficulty);
+        }
+
+        Ok(Self {
+            map,
+            mode,
+            attributes,
+        })
+    }
+
+    /// Mods that were not prepared are calculated without the cache
+    fn difficulty(&self, mods: GameMods) -> DifficultyAttributes {
+        match self.attributes.get(&mods) {
+            Some(attributes) => attributes.clone(),
+            None => calculate_difficulty(&self.map, mods, self.mode),
+        }
+    }
+
+    /// Star rating of the map in the mode with the given mods
+    pub fn stars(&self, mods: GameMods) -> f32 {
+        self.difficulty(mods).stars() as f32
+    }
+
+    /// Returns the pp for a full combo with the given accuracy.
+    /// Since mania pp depend on the score, the accuracy is used as score ratio.
+    pub fn pp_at_accuracy(&self, mods: GameMods, acc: f64) -> PerformanceAttributes {
+        let map = &self.map;
+        let attributes = self.difficulty(mods);
+        let mods = mods.bits();
+
+        match self.mode {
+            GameMode::STD => OsuPP::new(map)
+                .attributes(attributes)
+                .mods(mods)
+                .accuracy(acc)
+                .calculate()
+                .into(),
+            GameMode::TKO => TaikoPP::new(map)
+                .attributes(attributes)
+                .mods(mods)
+                .accuracy(acc)
+                .calculate()
+                .into(),
+            GameMode::CTB => FruitsPP::new(map)
+                .attributes(attributes)
+                .mods(mods)
+                .accuracy(acc)
+                .calculate()
+                .into(),
+            GameMode::MNA => ManiaPP::new(map)
+                .attributes(attributes)
+                .mods(mods)
+                .score((acc * 10_000.0) as u32)
+                .calculate()
+                .into(),
+        }
+    }
+
+    /// Returns the pp of the score and the max pp for its mods
+    pub fn calculate(&self, score: &Score) -> (f32, f32) {
+        let map = &self.map;
+        let attributes = self.difficulty(score.mods);
+        let max_pp = self.pp_at_accuracy(score.mods, 100.0).pp();
+        let stats = &score.statistics;
+        let mods = score.mods.bits();
+
+        let pp = match self.mode {
+            GameMode::STD => OsuPP::new(map)
+                .attributes(attributes)
+                .mods(mods)
+                .combo(score.max_combo as usize)
+                .misses(stats.count_miss as usize)
+                .n300(stats.count_300 as usize)
+                .n100(stats.count_100 as usize)
+                .n50(stats.count_50 as usize)
+                .calculate()
+                .pp(),
+            GameMode::TKO => TaikoPP::new(map)
+                .attributes(attributes)
+                .mods(mods)
+                .combo(score.max_combo as usize)
+                .misses(stats.count_miss as usize)
+                .n300(stats.count_300 as usize)
+                .n100(stats.count_100 as usize)
+                .calculate()
+                .pp(),
+            GameMode::CTB => FruitsPP::new(map)
+                .attributes(attributes)
+                .mods(mods)
+                .combo(score.max_combo as usize)
+                .misses(stats.count_miss as usize)
+                .fruits(stats.count_300 as usize)
+                .droplets(stats.count_100 as usize)
+                .tiny_droplets(stats.count_50 as usize)
+                .tiny_droplet_misses(stats.count_katu as usize)
+                .calculate()
+                .pp(),
+            GameMode::MNA => ManiaPP::new(map)
+                .attributes(attributes)
+                .mods(mods)
+                .score(score.score)
+                .calculate()
+                .pp(),
+        };
+
+        (pp as f32, max_pp as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_username_to_number() {
+        assert_eq!(
+            username_to_number("Some User"),
+            username_to_number("some_user")
+        );
+        assert_eq!(
+            username_to_number("BADEWANNE3"),
+            username_to_number("badewanne3")
+        );
+        assert_ne!(
+            username_to_number("some user"),
+            username_to_number("someuser")
+        );
+        assert_ne!(
+            username_to_number("some-user"),
+            username_to_number("some_user")
+        );
+    }
+}
@@ -0,0 +1,758 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use rosu_v2::prelude::{GameMode, GameMods, Score, User};
+
+use crate::{
+    context::Context,
+    database::{OsuVSDivisions, OsuVSMap, OsuVSMetric, OsuVSPlacement, OsuVSSeason},
+    error::BotResult,
+};
+
+use super::{
+    numbers::{round, with_comma_uint},
+    osu::{map_to_string, mode_name, PpCalculator},
+    pagination::Pages,
+    Author, EmbedBuilder, Footer, DESCRIPTION_SIZE, EMOTE_MEDALS, EMOTE_RANKS, OSUVS_CHANNEL,
+    OSU_BASE,
+};
+
+pub struct OsuVSEntry {
+    pub user_id: u32,
+    pub username: String,
+    pub score: Score,
+    pub pp: f32,
+    pub max_pp: f32,
+}
+
+/// Retrieve the best `count` scores of an osuvs map alongside the
+/// username of the player and the pp of the score
+pub async fn osuvs_leaderboard(
+    ctx: &Context,
+    osuvs_map: &OsuVSMap,
+    count: usize,
+) -> BotResult<Vec<OsuVSEntry>> {
+    let highscores = ctx
+        .database
+        .get_osuvs_highscores(osuvs_map.map_id, &osuvs_map.rules, count)
+        .await?;
+
+    if highscores.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let user_reqs = highscores
+        .iter()
+        .map(|(user_id, _)| ctx.osu.user(*user_id).mode(osuvs_map.mode));
+
+    let users = join_all(user_reqs).await;
+
+    let mods: Vec<_> = highscores.iter().map(|(_, score)| score.mods).collect();
+    let checksum = ctx.beatmap_store.verified_checksum(osuvs_map.map_id);
+    let calculator = PpCalculator::new(
+        ctx,
+        osuvs_map.map_id,
+        checksum.as_deref(),
+        osuvs_map.mode,
+        &mods,
+    )
+    .await?;
+
+    let entries = highscores
+        .into_iter()
+        .zip(users)
+        .map(|((user_id, score), user)| {
+            let username = match user {
+                Ok(user) => user.username,
+                Err(why) => {
+                    unwind_error!(warn, why, "Error when retrieving user for osuvs entry: {}");
+
+                    score_username(user_id, &score)
+                }
+            };
+
+            let (pp, max_pp) = calculator.calculate(&score);
+
+            OsuVSEntry {
+                user_id,
+                username,
+                score,
+                pp,
+                max_pp,
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Calculate the pp of the scores on the osuvs map and store them in the scores
+pub async fn calculate_pp(
+    ctx: &Context,
+    osuvs_map: &OsuVSMap,
+    scores: impl Iterator<Item = &mut Score>,
+) -> BotResult<()> {
+    let scores: Vec<_> = scores.collect();
+    let mods: Vec<_> = scores.iter().map(|score| score.mods).collect();
+    let checksum = ctx.beatmap_store.verified_checksum(osuvs_map.map_id);
+    let calculator = PpCalculator::new(
+        ctx,
+        osuvs_map.map_id,
+        checksum.as_deref(),
+        osuvs_map.mode,
+        &mods,
+    )
+    .await?;
+
+    for score in scores {
+        score.pp = Some(calculator.calculate(score).0);
+    }
+
+    Ok(())
+}
+
+/// The name of the player at the time the score was set
+pub fn score_username(user_id: u32, score: &Score) -> String {
+    score
+        .user
+        .as_ref()
+        .map_or_else(|| user_id.to_string(), |user| user.username.clone())
+}
+
+/// Title of the osuvs map, taken from the score if it contains the map
+pub async fn map_title(ctx: &Context, osuvs_map: &OsuVSMap, score: &Score) -> BotResult<String> {
+    let title = score
+        .map
+        .as_ref()
+        .zip(score.mapset.as_ref())
+        .map(|(map, mapset)| format!("{} - {} [{}]", mapset.artist, mapset.title, map.version));
+
+    match title {
+        Some(title) => Ok(title),
+        None => Ok(map_to_string(
+            &ctx.osu.beatmap().map_id(osuvs_map.map_id).await?,
+        )),
+    }
+}
+
+/// Describe how the placement of a player changed after a new score
+pub fn rank_change(prev_idx: Option<usize>, new_idx: usize) -> String {
+    match prev_idx {
+        Some(idx) if idx > new_idx => format!(
+            "You climbed from rank #{} to rank **#{}**!",
+            idx + 1,
+            new_idx + 1
+        ),
+        Some(_) => format!("You remain at rank **#{}**.", new_idx + 1),
+        None => format!("You entered the leaderboard at rank **#{}**!", new_idx + 1),
+    }
+}
+
+/// Store a new best score of the user. `highscores` must be the full leaderboard
+/// of the osuvs map sorted by its rules and will be updated accordingly.
+/// If the score reaches the top 3, it is announced in the osuvs channel.
+///
+/// Returns the previous and the new index of the user on the leaderboard.
+pub async fn submit_score(
+    ctx: &Context,
+    osuvs_map: &OsuVSMap,
+    highscores: &mut Vec<(u32, Score)>,
+    user_id: u32,
+    score: Score,
+) -> BotResult<(Option<usize>, usize)> {
+    let rules = &osuvs_map.rules;
+
+    if let Err(why) = assign_division(ctx, osuvs_map, user_id).await {
+        unwind_error!(warn, why, "Error while assigning OsuVS division: {}");
+    }
+
+    ctx.database
+        .insert_osuvs_highscores(osuvs_map.map_id, user_id, score.clone())
+        .await?;
+
+    let prev_idx = highscores.iter().position(|(id, _)| *id == user_id);
+
+    match prev_idx {
+        Some(idx) => highscores[idx].1 = score,
+        None => highscores.push((user_id, score)),
+    }
+
+    highscores.sort_by(|(_, a), (_, b)| rules.cmp(a, b));
+
+    let new_idx = highscores
+        .iter()
+        .position(|(id, _)| *id == user_id)
+        .unwrap_or(0);
+
+    let climbed = !matches!(prev_idx, Some(idx) if idx <= new_idx);
+
+    if climbed && new_idx < EMOTE_MEDALS.len() {
+        if let Err(why) = announce_top_score(ctx, osuvs_map, highscores, new_idx).await {
+            unwind_error!(error, why, "Error while announcing new OsuVS top score: {}");
+        }
+    }
+
+    Ok((prev_idx, new_idx))
+}
+
+/// Post the score at `idx` to the osuvs channel alongside the player it overtook
+async fn announce_top_score(
+    ctx: &Context,
+    osuvs_map: &OsuVSMap,
+    highscores: &[(u32, Score)],
+    idx: usize,
+) -> BotResult<()> {
+    let (user_id, score) = &highscores[idx];
+    let map = ctx.osu.beatmap().map_id(osuvs_map.map_id).await?;
+
+    let calculator = PpCalculator::new(
+        ctx,
+        osuvs_map.map_id,
+        map.checksum.as_deref(),
+        osuvs_map.mode,
+        &[score.mods],
+    )
+    .await?;
+    let (pp, max_pp) = calculator.calculate(score);
+
+    let entry = OsuVSEntry {
+        user_id: *user_id,
+        username: score_username(*user_id, score),
+        score: score.clone(),
+        pp,
+        max_pp,
+    };
+
+    info!(
+        "Announcing new osuvs #{} of user {}...",
+        idx + 1,
+        entry.username
+    );
+
+    let mut description = String::new();
+    write_entry(
+        &mut description,
+        EMOTE_MEDALS[idx],
+        &entry,
+        map.max_combo.unwrap_or(0),
+    );
+
+    if let Some((overtaken_id, overtaken_score)) = highscores.get(idx + 1) {
+        let _ = write!(
+            description,
+            "\nOvertaking [{}]({}users/{})",
+            score_username(*overtaken_id, overtaken_score),
+            OSU_BASE,
+            overtaken_id
+        );
+    }
+
+    let author = if idx == 0 {
+        format!(
+            "{} took #1 on the {} OsuVS!",
+            entry.username,
+            mode_name(osuvs_map.mode)
+        )
+    } else {
+        format!(
+            "{} climbed to #{} on the {} OsuVS!",
+            entry.username,
+            idx + 1,
+            mode_name(osuvs_map.mode)
+        )
+    };
+
+    let builder = EmbedBuilder::new()
+        .title(map_to_string(&map))
+        .url(format!("{}b/{}", OSU_BASE, osuvs_map.map_id))
+        .thumbnail(format!("https://a.ppy.sh/{}", user_id))
+        .author(Author::new(author))
+        .description(description);
+
+    ctx.http
+        .create_message(OSUVS_CHANNEL)
+        .embeds(&[builder.build()])?
+        .exec()
+        .await?;
+
+    Ok(())
+}
+
+/// Put all linked players into the divisions configured for the mode of the round.
+/// Divisions are snapshotted only once so that they can't change mid-round.
+pub async fn snapshot_divisions(ctx: &Context, osuvs_map: &OsuVSMap) -> BotResult<()> {
+    let map_id = osuvs_map.map_id;
+
+    let divisions = match ctx.database.get_osuvs_map_divisions(map_id).await? {
+        Some(divisions) => divisions,
+        None => {
+            let divisions = ctx
+                .database
+                .get_osuvs_division_config(osuvs_map.mode)
+                .await?;
+
+            if divisions.is_empty() {
+                return Ok(());
+            }
+
+            ctx.database
+                .insert_osuvs_map_divisions(map_id, &divisions)
+                .await?;
+
+            divisions
+        }
+    };
+
+    info!("Snapshotting divisions of osuvs map {}...", map_id);
+
+    let osu_ids: HashSet<u32> = ctx
+        .database
+        .get_manual_links()
+        .await?
+        .into_iter()
+        .map(|(_, osu_id)| osu_id)
+        .collect();
+
+    let user_reqs = osu_ids
+        .iter()
+        .map(|&osu_id| ctx.osu.user(osu_id).mode(osuvs_map.mode));
+
+    let users = join_all(user_reqs).await;
+
+    let members: Vec<_> = osu_ids
+        .into_iter()
+        .zip(users)
+        .filter_map(|(osu_id, user)| match user {
+            Ok(user) => Some((osu_id, player_division(&divisions, &user))),
+            Err(why) => {
+                unwind_error!(
+                    warn,
+                    why,
+                    "Error when retrieving user for osuvs division: {}"
+                );
+
+                None
+            }
+        })
+        .collect();
+
+    ctx.database
+        .insert_osuvs_division_members(map_id, &members)
+        .await
+}
+
+/// Players without a division, e.g. because they linked their account
+/// mid-round, are put into one when they submit their first score
+async fn assign_division(ctx: &Context, osuvs_map: &OsuVSMap, user_id: u32) -> BotResult<()> {
+    let map_id = osuvs_map.map_id;
+
+    let divisions = match ctx.database.get_osuvs_map_divisions(map_id).await? {
+        Some(divisions) => divisions,
+        None => return Ok(()),
+    };
+
+    if ctx
+        .database
+        .get_osuvs_division_member(map_id, user_id)
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    let user = ctx.osu.user(user_id).mode(osuvs_map.mode).await?;
+    let division = player_division(&divisions, &user);
+
+    ctx.database
+        .insert_osuvs_division_members(map_id, &[(user_id, division)])
+        .await
+}
+
+fn player_division(divisions: &OsuVSDivisions, user: &User) -> u8 {
+    match user.statistics {
+        Some(ref stats) => divisions.division(stats.global_rank, stats.pp),
+        None => divisions.division(None, 0.0),
+    }
+}
+
+/// DM the discord users linked to the player that opted into notifications
+/// about their improved score. Errors are logged instead of propagated.
+pub async fn notify_improvement(
+    ctx: &Context,
+    osuvs_map: &OsuVSMap,
+    user_id: u32,
+    score: &Score,
+    (prev_idx, new_idx): (Option<usize>, usize),
+) {
+    let targets = match ctx.database.get_osuvs_notification_targets(user_id).await {
+        Ok(targets) if targets.is_empty() => return,
+        Ok(targets) => targets,
+        Err(why) => {
+            unwind_error!(
+                error,
+                why,
+                "Error while getting OsuVS notification targets: {}"
+            );
+
+            return;
+        }
+    };
+
+    let title = match map_title(ctx, osuvs_map, score).await {
+        Ok(title) => title,
+        Err(why) => {
+            unwind_error!(warn, why, "Error while getting title of OsuVS map: {}");
+
+            return;
+        }
+    };
+
+    let description = format!(
+        "Your new score with {} on [{}]({}b/{}) has been registered!\n{}",
+        metric_value(osuvs_map.rules.metric, score),
+        title,
+        OSU_BASE,
+        osuvs_map.map_id,
+        rank_change(prev_idx, new_idx)
+    );
+
+    let author = format!("{} OsuVS score improved", mode_name(osuvs_map.mode));
+    let embed = EmbedBuilder::new()
+        .author(Author::new(author))
+        .description(description)
+        .build();
+
+    for discord_id in targets {
+        if let Err(why) = ctx.send_dm(discord_id, embed.clone()).await {
+            unwind_error!(
+                warn,
+                why,
+                "Could not notify user {} about their OsuVS score: {}",
+                discord_id
+            );
+        }
+    }
+}
+
+/// Write a two line description of the entry, starting with `prefix`
+pub fn write_entry(description: &mut String, prefix: &str, entry: &OsuVSEntry, max_combo: u32) {
+    let score = &entry.score;
+
+    let _ = writeln!(
+        description,
+        "{} {} [{}]({}users/{}): {} [ **{}x**/{}x ] **+{}**\n\
+        - **{}**/{}PP - {}% - <t:{}:R>",
+        prefix,
+        EMOTE_RANKS[&score.grade],
+        entry.username,
+        OSU_BASE,
+        entry.user_id,
+        with_comma_uint(score.score),
+        score.max_combo,
+        max_combo,
+        score.mods,
+        round(entry.pp),
+        round(entry.max_pp),
+        round(score.accuracy),
+        score.created_at.timestamp()
+    );
+}
+
+/// Description for a page of a leaderboard. The top 3 are prefixed with medals
+/// and the entry of `highlight` is marked. Entries that would exceed the
+/// description size are left out.
+pub fn leaderboard_page(
+    entries: &[OsuVSEntry],
+    pages: &Pages,
+    max_combo: u32,
+    highlight: Option<u32>,
+) -> String {
+    let mut description = String::new();
+    let page = entries.iter().skip(pages.index).take(pages.per_page);
+
+    for (entry, i) in page.zip(pages.index..) {
+        let mut prefix = match EMOTE_MEDALS.get(i) {
+            Some(medal) => (*medal).to_owned(),
+            None => format!("**{}.**", i + 1),
+        };
+
+        if highlight == Some(entry.user_id) {
+            prefix.insert_str(0, "➤ ");
+        }
+
+        let mut line = String::new();
+        write_entry(&mut line, &prefix, entry, max_combo);
+
+        if description.len() + line.len() > DESCRIPTION_SIZE {
+            break;
+        }
+
+        description.push_str(&line);
+    }
+
+    description
+}
+
+/// The value of the score for the metric e.g. `98.76%` for accuracy
+pub fn metric_value(metric: OsuVSMetric, score: &Score) -> String {
+    match metric {
+        OsuVSMetric::Score => format!("**{}** score", with_comma_uint(score.score)),
+        OsuVSMetric::Accuracy => format!("**{}%**", round(score.accuracy)),
+        OsuVSMetric::Pp => format!("**{}**PP", round(score.pp.unwrap_or(0.0))),
+        OsuVSMetric::Combo => format!("**{}x** combo", score.max_combo),
+        OsuVSMetric::Misses => format!("**{}** misses", score.statistics.count_miss),
+    }
+}
+
+pub struct StandingsEntry {
+    pub user_id: u32,
+    pub username: String,
+    pub points: u32,
+    pub rounds: usize,
+    pub wins: usize,
+}
+
+/// Accumulate the points of all rounds of the season in the mode
+/// that ended until the given date, sorted by points.
+/// If a division is given, only players of that division are considered.
+pub async fn season_standings(
+    ctx: &Context,
+    season: &OsuVSSeason,
+    mode: GameMode,
+    until: DateTime<Utc>,
+    division: Option<u8>,
+) -> BotResult<Vec<StandingsEntry>> {
+    let osuvs_maps = ctx
+        .database
+        .get_season_osuvs_maps(season, mode, until)
+        .await?;
+
+    let mut standings: HashMap<u32, StandingsEntry> = HashMap::new();
+
+    for osuvs_map in osuvs_maps {
+        let mut highscores = ctx
+            .database
+            .get_osuvs_highscores(osuvs_map.map_id, &osuvs_map.rules, usize::MAX)
+            .await?;
+
+        // Placements are only counted within the division
+        if let Some(division) = division {
+            let members = ctx
+                .database
+                .get_osuvs_division_members(osuvs_map.map_id)
+                .await?;
+
+            highscores.retain(|(user_id, _)| members.get(user_id) == Some(&division));
+        }
+
+        for ((user_id, score), placement) in highscores.into_iter().zip(0..) {
+            let entry = standings.entry(user_id).or_insert_with(|| StandingsEntry {
+                user_id,
+                username: user_id.to_string(),
+                points: 0,
+                rounds: 0,
+                wins: 0,
+            });
+
+            // Rounds are ordered by date so the most recent name is kept
+            if let Some(user) = score.user {
+                entry.username = user.username;
+            }
+
+            entry.points += season.placement_points(placement);
+            entry.rounds += 1;
+            entry.wins += (placement == 0) as usize;
+        }
+    }
+
+    let mut standings: Vec<_> = standings.into_values().collect();
+
+    standings.sort_unstable_by(|a, b| {
+        b.points
+            .cmp(&a.points)
+            .then_with(|| b.wins.cmp(&a.wins))
+            .then_with(|| a.username.cmp(&b.username))
+    });
+
+    Ok(standings)
+}
+
+pub fn standings_embed(
+    season: &OsuVSSeason,
+    mode: GameMode,
+    division: Option<u8>,
+    standings: &[StandingsEntry],
+    pages: &Pages,
+) -> EmbedBuilder {
+    let mut description = String::new();
+    let page = standings.iter().skip(pages.index).take(pages.per_page);
+
+    for (entry, i) in page.zip(pages.index + 1..) {
+        let _ = writeln!(
+            description,
+            "**{}.** [{}]({}users/{}): **{}** points • {} round{} • {} win{}",
+            i,
+            entry.username,
+            OSU_BASE,
+            entry.user_id,
+            entry.points,
+            entry.rounds,
+            if entry.rounds == 1 { "" } else { "s" },
+            entry.wins,
+            if entry.wins == 1 { "" } else { "s" },
+        );
+    }
+
+    if standings.is_empty() {
+        description.push_str("No points have been awarded yet!");
+    }
+
+    let mut author = format!("{} OsuVS standings of {}", mode_name(mode), season.name);
+
+    if let Some(division) = division {
+        let _ = write!(author, " • Division {}", division);
+    }
+
+    let footer = format!(
+        "{} • {} until {}",
+        pages.footer_text(),
+        season.start_date.format("%F"),
+        season.end_date.format("%F")
+    );
+
+    EmbedBuilder::new()
+        .author(Author::new(author))
+        .description(description)
+        .footer(Footer::new(footer))
+}
+
+/// OsuVS career of a player across all finished rounds of a mode
+pub struct OsuVSCareer {
+    pub username: Option<String>,
+    pub rounds: usize,
+    pub total_rounds: usize,
+    pub wins: usize,
+    pub podiums: usize,
+    placement_sum: usize,
+    /// Best placement (0-based) and the map it was achieved on
+    pub best: Option<(usize, u32)>,
+    /// Most played mods alongside how often they were played
+    pub favourite_mods: Option<(GameMods, usize)>,
+    pub current_streak: usize,
+    pub longest_streak: usize,
+}
+
+impl OsuVSCareer {
+    pub fn average_placement(&self) -> Option<f32> {
+        (self.rounds > 0).then(|| self.placement_sum as f32 / self.rounds as f32 + 1.0)
+    }
+}
+
+/// Collect the career of the player from their placements as returned
+/// by [`get_osuvs_career`](crate::database::Database::get_osuvs_career)
+pub fn osuvs_career(rounds: &[Option<OsuVSPlacement>]) -> OsuVSCareer {
+    let mut career = OsuVSCareer {
+        username: None,
+        rounds: 0,
+        total_rounds: rounds.len(),
+        wins: 0,
+        podiums: 0,
+        placement_sum: 0,
+        best: None,
+        favourite_mods: None,
+        current_streak: 0,
+        longest_streak: 0,
+    };
+
+    let mut mods_count: HashMap<GameMods, usize> = HashMap::new();
+
+    for played in rounds {
+        let played = match played {
+            Some(played) => played,
+            None => {
+                career.current_streak = 0;
+
+                continue;
+            }
+        };
+
+        let placement = played.placement;
+
+        // Rounds are ordered by date so the most recent name is kept
+        if played.username.is_some() {
+            career.username = played.username.clone();
+        }
+
+        career.rounds += 1;
+        career.wins += (placement == 0) as usize;
+        career.podiums += (placement < 3) as usize;
+        career.placement_sum += placement;
+        career.current_streak += 1;
+        career.longest_streak = career.longest_streak.max(career.current_streak);
+
+        if !matches!(career.best, Some((best, _)) if best <= placement) {
+            career.best = Some((placement, played.map_id));
+        }
+
+        *mods_count.entry(played.mods).or_insert(0) += 1;
+    }
+
+    career.favourite_mods = mods_count.into_iter().max_by(|(mods_a, a), (mods_b, b)| {
+        a.cmp(b).then_with(|| mods_b.bits().cmp(&mods_a.bits()))
+    });
+
+    career
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played(map_id: u32, placement: usize, mods: GameMods) -> Option<OsuVSPlacement> {
+        Some(OsuVSPlacement {
+            map_id,
+            placement,
+            username: Some(format!("name{}", map_id)),
+            mods,
+        })
+    }
+
+    #[test]
+    fn test_osuvs_career() {
+        let rounds = [
+            played(1, 2, GameMods::Hidden),
+            played(2, 0, GameMods::Hidden),
+            None,
+            played(4, 5, GameMods::NoMod),
+            played(5, 0, GameMods::Hidden),
+        ];
+
+        let career = osuvs_career(&rounds);
+
+        assert_eq!(career.username.as_deref(), Some("name5"));
+        assert_eq!(career.rounds, 4);
+        assert_eq!(career.total_rounds, 5);
+        assert_eq!(career.wins, 2);
+        assert_eq!(career.podiums, 3);
+        assert_eq!(career.average_placement(), Some(2.75));
+        assert_eq!(career.best, Some((0, 2)));
+        assert_eq!(career.favourite_mods, Some((GameMods::Hidden, 3)));
+        assert_eq!(career.current_streak, 2);
+        assert_eq!(career.longest_streak, 2);
+    }
+
+    #[test]
+    fn test_osuvs_career_empty() {
+        let career = osuvs_career(&[None, None]);
+
+        assert_eq!(career.rounds, 0);
+        assert_eq!(career.total_rounds, 2);
+        assert_eq!(career.average_placement(), None);
+        assert_eq!(career.best, None);
+        assert_eq!(career.favourite_mods, None);
+    }
+}
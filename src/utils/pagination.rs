@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use tokio::time::timeout;
+use twilight_gateway::Event;
+use twilight_model::{
+    application::{
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+        interaction::{Interaction, MessageComponentInteraction},
+    },
+    id::{InteractionId, UserId},
+};
+use twilight_standby::WaitForEventStream;
+
+use crate::context::Context;
+
+use super::numbers::{div_euclid, last_multiple};
+
+/// How long to wait for the next button press before giving up
+pub const PAGINATION_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub const BUTTON_PREV: &str = "pagination_prev";
+pub const BUTTON_NEXT: &str = "pagination_next";
+
+#[derive(Copy, Clone, Debug)]
+pub struct Pages {
+    pub index: usize,
+    pub per_page: usize,
+    pub total: usize,
+}
+
+impl Pages {
+    pub fn new(per_page: usize, total: usize) -> Self {
+        Self {
+            index: 0,
+            per_page,
+            total,
+        }
+    }
+
+    pub fn curr_page(&self) -> usize {
+        self.index / self.per_page + 1
+    }
+
+    pub fn last_page(&self) -> usize {
+        div_euclid(self.per_page, self.total)
+    }
+
+    pub fn last_index(&self) -> usize {
+        last_multiple(self.per_page, self.total)
+    }
+
+    pub fn next(&mut self) {
+        self.index = (self.index + self.per_page).min(self.last_index());
+    }
+
+    pub fn prev(&mut self) {
+        self.index = self.index.saturating_sub(self.per_page);
+    }
+
+    /// Jump to the page containing the entry at position `idx`
+    pub fn jump_to(&mut self, idx: usize) {
+        self.index = (idx - idx % self.per_page).min(self.last_index());
+    }
+
+    /// Adjust the total amount of entries e.g. after some were removed
+    pub fn set_total(&mut self, total: usize) {
+        self.total = total;
+        self.index = self.index.min(self.last_index());
+    }
+
+    /// Apply a pressed pagination button, returns `false` if the button
+    /// was not one of the pagination buttons
+    pub fn update(&mut self, custom_id: &str) -> bool {
+        match custom_id {
+            BUTTON_PREV => self.prev(),
+            BUTTON_NEXT => self.next(),
+            _ => return false,
+        }
+
+        true
+    }
+
+    pub fn footer_text(&self) -> String {
+        format!("Page {}/{}", self.curr_page(), self.last_page())
+    }
+}
+
+pub fn page_buttons(pages: &Pages) -> Component {
+    let prev = Button {
+        custom_id: Some(BUTTON_PREV.to_owned()),
+        disabled: pages.index == 0,
+        emoji: None,
+        label: Some("◀".to_owned()),
+        style: ButtonStyle::Secondary,
+        url: None,
+    };
+
+    let next = Button {
+        custom_id: Some(BUTTON_NEXT.to_owned()),
+        disabled: pages.index >= pages.last_index(),
+        emoji: None,
+        label: Some("▶".to_owned()),
+        style: ButtonStyle::Secondary,
+        url: None,
+    };
+
+    Component::ActionRow(ActionRow {
+        components: vec![Component::Button(prev), Component::Button(next)],
+    })
+}
+
+/// Stream of component interactions on the response to a specific command
+/// that were triggered by a specific user
+pub struct ComponentStream(WaitForEventStream);
+
+impl ComponentStream {
+    /// Should be created before responding to the command so that no
+    /// interaction can slip through
+    pub fn new(ctx: &Context, interaction_id: InteractionId, user_id: UserId) -> Self {
+        let stream = ctx.standby.wait_for_event_stream(move |event: &Event| {
+            if let Event::InteractionCreate(e) = event {
+                if let Interaction::MessageComponent(component) = &e.0 {
+                    let same_message = component.message.interaction.as_ref().map(|i| i.id)
+                        == Some(interaction_id);
+
+                    return same_message && component.author_id() == Some(user_id);
+                }
+            }
+
+            false
+        });
+
+        Self(stream)
+    }
+
+    /// Wait for the next interaction, `None` if the timeout expired
+    pub async fn next(&mut self) -> Option<Box<MessageComponentInteraction>> {
+        loop {
+            match timeout(PAGINATION_TIMEOUT, self.0.next()).await {
+                Ok(Some(Event::InteractionCreate(e))) => {
+                    if let Interaction::MessageComponent(component) = e.0 {
+                        return Some(component);
+                    }
+                }
+                Ok(Some(_)) => {}
+                Ok(None) | Err(_) => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages_last_page() {
+        assert_eq!(Pages::new(10, 0).last_page(), 1);
+        assert_eq!(Pages::new(10, 10).last_page(), 1);
+        assert_eq!(Pages::new(10, 11).last_page(), 2);
+        assert_eq!(Pages::new(10, 20).last_page(), 2);
+    }
+
+    #[test]
+    fn test_pages_last_index() {
+        assert_eq!(Pages::new(10, 0).last_index(), 0);
+        assert_eq!(Pages::new(10, 9).last_index(), 0);
+        assert_eq!(Pages::new(10, 20).last_index(), 10);
+        assert_eq!(Pages::new(10, 21).last_index(), 20);
+    }
+
+    #[test]
+    fn test_pages_navigation() {
+        let mut pages = Pages::new(5, 12);
+
+        pages.prev();
+        assert_eq!(pages.index, 0);
+
+        assert!(pages.update(BUTTON_NEXT));
+        assert!(pages.update(BUTTON_NEXT));
+        assert!(pages.update(BUTTON_NEXT));
+        assert_eq!(pages.index, 10);
+        assert_eq!(pages.footer_text(), "Page 3/3");
+
+        assert!(!pages.update("accept_1"));
+
+        pages.set_total(7);
+        assert_eq!(pages.index, 5);
+
+        pages.jump_to(3);
+        assert_eq!(pages.curr_page(), 1);
+    }
+
+    #[test]
+    fn test_pages_empty() {
+        let mut pages = Pages::new(10, 0);
+
+        pages.next();
+        assert_eq!(pages.index, 0);
+        assert_eq!(pages.footer_text(), "Page 1/1");
+
+        pages.jump_to(5);
+        assert_eq!(pages.index, 0);
+    }
+}